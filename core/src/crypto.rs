@@ -1,94 +1,451 @@
-use ed25519_dalek::{Keypair, PublicKey as Ed25519PublicKey, SecretKey, Signature, Verifier};
+use ed25519_dalek::{
+    Keypair, PublicKey as Ed25519PublicKey, SecretKey, Signature as Ed25519Signature,
+    Signer as Ed25519Signer, Verifier as Ed25519Verifier,
+};
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::ecdsa::signature::{Signer as Secp256k1Signer, Verifier as Secp256k1Verifier};
+use k256::ecdsa::{RecoveryId, Signature as Secp256k1Signature, SigningKey, VerifyingKey};
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A 32-byte secret key buffer that is zeroed on drop, so the raw key
+/// material doesn't linger in memory once it's out of scope. There is no
+/// `Deref`/`AsRef` impl -- call [`expose_secret`](Self::expose_secret)
+/// explicitly to read the bytes, the same guard pattern the `secrecy`
+/// crate uses to make leaking a secret an opt-in act at the call site.
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct SecretKeyBytes([u8; 32]);
+
+impl SecretKeyBytes {
+    /// Exposes the raw secret bytes. Callers should avoid copying the
+    /// result anywhere that outlives this guard.
+    pub fn expose_secret(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretKeyBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKeyBytes(REDACTED)")
+    }
+}
+
+/// Elliptic curve backing a [`PublicKey`]/[`KeyPair`]. Ed25519 is the
+/// SDUPI-native default; Secp256k1 lets validators and wallets interoperate
+/// with EVM-style chains (see [`wallet_integrations`](crate::wallet_integrations)
+/// for the ecrecover-compatible wallet-adapter path, which is separate from
+/// this core key abstraction).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyType {
+    Ed25519,
+    Secp256k1,
+}
+
+impl KeyType {
+    fn discriminant(self) -> u8 {
+        match self {
+            KeyType::Ed25519 => 0,
+            KeyType::Secp256k1 => 1,
+        }
+    }
+
+    fn from_discriminant(byte: u8) -> Result<Self, crate::SDUPIError> {
+        match byte {
+            0 => Ok(KeyType::Ed25519),
+            1 => Ok(KeyType::Secp256k1),
+            other => Err(crate::SDUPIError::Crypto(format!("Unknown key type discriminant: {}", other))),
+        }
+    }
+
+    /// Infers the key type from a bare (untagged) key's length: 32 bytes is
+    /// an Ed25519 point, 33 bytes is a compressed SEC1 secp256k1 point.
+    fn from_bare_len(len: usize) -> Result<Self, crate::SDUPIError> {
+        match len {
+            32 => Ok(KeyType::Ed25519),
+            33 => Ok(KeyType::Secp256k1),
+            other => Err(crate::SDUPIError::Crypto(format!("Invalid public key length: {}", other))),
+        }
+    }
+
+    /// Multicodec code identifying this key type's encoding, per the
+    /// [multicodec table](https://github.com/multiformats/multicodec/blob/master/table.csv)
+    /// (`ed25519-pub` = 0xed, `secp256k1-pub` = 0xe7).
+    fn multicodec(self) -> u64 {
+        match self {
+            KeyType::Ed25519 => 0xed,
+            KeyType::Secp256k1 => 0xe7,
+        }
+    }
+
+    fn from_multicodec(code: u64) -> Result<Self, crate::SDUPIError> {
+        match code {
+            0xed => Ok(KeyType::Ed25519),
+            0xe7 => Ok(KeyType::Secp256k1),
+            other => Err(crate::SDUPIError::Crypto(format!("Unknown multicodec key type: 0x{:x}", other))),
+        }
+    }
+}
+
+/// Appends `value` to `out` as an unsigned LEB128 varint, per the
+/// [multiformats varint spec](https://github.com/multiformats/unsigned-varint).
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Reads an unsigned LEB128 varint from the front of `bytes`, returning the
+/// decoded value and the remaining bytes.
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), crate::SDUPIError> {
+    let mut value: u64 = 0;
+    let mut shift = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+        shift += 7;
+    }
+    Err(crate::SDUPIError::Crypto("Truncated multicodec varint".to_string()))
+}
+
+impl fmt::Display for KeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyType::Ed25519 => write!(f, "ed25519"),
+            KeyType::Secp256k1 => write!(f, "secp256k1"),
+        }
+    }
+}
+
+impl FromStr for KeyType {
+    type Err = crate::SDUPIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ed25519" => Ok(KeyType::Ed25519),
+            "secp256k1" => Ok(KeyType::Secp256k1),
+            other => Err(crate::SDUPIError::Crypto(format!("Unknown key type: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PublicKeyInner {
+    Ed25519(Ed25519PublicKey),
+    Secp256k1(VerifyingKey),
+}
 
 /// Public key for SDUPI blockchain
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
 pub struct PublicKey {
-    inner: Ed25519PublicKey,
+    inner: PublicKeyInner,
 }
 
 /// Key pair for signing transactions
 #[derive(Debug, Clone)]
 pub struct KeyPair {
-    inner: Keypair,
+    inner: KeyPairInner,
+}
+
+#[derive(Debug, Clone)]
+enum KeyPairInner {
+    Ed25519(Keypair),
+    Secp256k1(SigningKey),
 }
 
 impl PublicKey {
-    /// Create a public key from bytes
+    /// Create a public key from bytes, inferring the curve from length (32
+    /// bytes -> ed25519, 33 bytes -> compressed secp256k1).
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::SDUPIError> {
-        let inner = Ed25519PublicKey::from_bytes(bytes)
-            .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid public key bytes: {}", e)))?;
+        Self::from_typed_bytes(KeyType::from_bare_len(bytes.len())?, bytes)
+    }
+
+    /// Create a public key from bytes of an explicitly-known `key_type`.
+    pub fn from_typed_bytes(key_type: KeyType, bytes: &[u8]) -> Result<Self, crate::SDUPIError> {
+        let inner = match key_type {
+            KeyType::Ed25519 => {
+                let key = Ed25519PublicKey::from_bytes(bytes)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid public key bytes: {}", e)))?;
+                PublicKeyInner::Ed25519(key)
+            }
+            KeyType::Secp256k1 => {
+                let key = VerifyingKey::from_sec1_bytes(bytes)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid public key bytes: {}", e)))?;
+                PublicKeyInner::Secp256k1(key)
+            }
+        };
         Ok(Self { inner })
     }
-    
-    /// Get public key as bytes
-    pub fn to_bytes(&self) -> [u8; 32] {
-        self.inner.to_bytes()
+
+    /// Create a public key from its 1-byte-discriminant-prefixed
+    /// serialization (see [`to_prefixed_bytes`](Self::to_prefixed_bytes)).
+    pub fn from_prefixed_bytes(bytes: &[u8]) -> Result<Self, crate::SDUPIError> {
+        let (discriminant, key_bytes) = bytes
+            .split_first()
+            .ok_or_else(|| crate::SDUPIError::Crypto("Empty public key bytes".to_string()))?;
+        Self::from_typed_bytes(KeyType::from_discriminant(*discriminant)?, key_bytes)
+    }
+
+    /// The curve this public key was generated on.
+    pub fn key_type(&self) -> KeyType {
+        match &self.inner {
+            PublicKeyInner::Ed25519(_) => KeyType::Ed25519,
+            PublicKeyInner::Secp256k1(_) => KeyType::Secp256k1,
+        }
+    }
+
+    /// Get public key as bytes: the raw 32-byte point for ed25519, the
+    /// compressed 33-byte SEC1 point for secp256k1.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match &self.inner {
+            PublicKeyInner::Ed25519(key) => key.to_bytes().to_vec(),
+            PublicKeyInner::Secp256k1(key) => key.to_encoded_point(true).as_bytes().to_vec(),
+        }
+    }
+
+    /// Get the 1-byte-discriminant-prefixed serialization, so the key type
+    /// round-trips through storage/wire formats alongside the bytes.
+    pub fn to_prefixed_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.key_type().discriminant()];
+        bytes.extend(self.to_bytes());
+        bytes
+    }
+
+    /// Encodes this key as a `did:key:z...` identifier: a multicodec varint
+    /// tagging the curve, followed by the raw key bytes, base58btc-encoded
+    /// with the `z` multibase prefix. Portable across services and config
+    /// files, unlike the bare hex this type used to print.
+    pub fn to_did_key(&self) -> String {
+        let mut bytes = Vec::new();
+        write_varint(self.key_type().multicodec(), &mut bytes);
+        bytes.extend(self.to_bytes());
+        format!("did:key:z{}", bs58::encode(bytes).into_string())
+    }
+
+    /// Parses a `did:key:z...` identifier produced by
+    /// [`to_did_key`](Self::to_did_key), inferring the `KeyType` from its
+    /// multicodec prefix.
+    pub fn from_did_key(s: &str) -> Result<Self, crate::SDUPIError> {
+        let encoded = s
+            .strip_prefix("did:key:z")
+            .ok_or_else(|| crate::SDUPIError::Crypto("Not a did:key z-base58btc identifier".to_string()))?;
+
+        let bytes = bs58::decode(encoded)
+            .into_vec()
+            .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid base58btc in did:key: {}", e)))?;
+
+        let (codec, key_bytes) = read_varint(&bytes)?;
+        Self::from_typed_bytes(KeyType::from_multicodec(codec)?, key_bytes)
     }
-    
+
     /// Verify a signature
     pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), crate::SDUPIError> {
-        let sig = Signature::from_bytes(signature)
-            .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid signature: {}", e)))?;
-        
-        self.inner
-            .verify(message, &sig)
-            .map_err(|e| crate::SDUPIError::Crypto(format!("Signature verification failed: {}", e)))?;
-        
+        match &self.inner {
+            PublicKeyInner::Ed25519(key) => {
+                let sig = Ed25519Signature::from_bytes(signature)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid signature: {}", e)))?;
+
+                key.verify(message, &sig)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Signature verification failed: {}", e)))?;
+            }
+            PublicKeyInner::Secp256k1(key) => {
+                let sig = Secp256k1Signature::from_slice(signature)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid signature: {}", e)))?;
+
+                key.verify(message, &sig)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Signature verification failed: {}", e)))?;
+            }
+        }
+
         Ok(())
     }
 }
 
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_prefixed_bytes() == other.to_prefixed_bytes()
+    }
+}
+
+impl Eq for PublicKey {}
+
+impl std::hash::Hash for PublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.to_prefixed_bytes().hash(state);
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_prefixed_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Self::from_prefixed_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
 impl KeyPair {
-    /// Generate a new key pair
+    /// Generate a new ed25519 key pair (the SDUPI-native default; use
+    /// [`generate_with_type`](Self::generate_with_type) to pick secp256k1).
     pub fn generate() -> Self {
-        let inner = Keypair::generate(&mut OsRng);
+        Self::generate_with_type(KeyType::Ed25519)
+    }
+
+    /// Generate a new key pair on the given curve.
+    pub fn generate_with_type(key_type: KeyType) -> Self {
+        let inner = match key_type {
+            KeyType::Ed25519 => KeyPairInner::Ed25519(Keypair::generate(&mut OsRng)),
+            KeyType::Secp256k1 => KeyPairInner::Secp256k1(SigningKey::random(&mut OsRng)),
+        };
         Self { inner }
     }
-    
-    /// Create key pair from secret key bytes
+
+    /// The curve this key pair was generated on.
+    pub fn key_type(&self) -> KeyType {
+        match &self.inner {
+            KeyPairInner::Ed25519(_) => KeyType::Ed25519,
+            KeyPairInner::Secp256k1(_) => KeyType::Secp256k1,
+        }
+    }
+
+    /// Create an ed25519 key pair from secret key bytes (use
+    /// [`from_typed_secret_key_bytes`](Self::from_typed_secret_key_bytes) for
+    /// secp256k1).
     pub fn from_secret_key_bytes(bytes: &[u8]) -> Result<Self, crate::SDUPIError> {
-        let secret_key = SecretKey::from_bytes(bytes)
-            .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid secret key: {}", e)))?;
-        
-        let public_key = (&secret_key).into();
-        let inner = Keypair {
-            secret: secret_key,
-            public: public_key,
+        Self::from_typed_secret_key_bytes(KeyType::Ed25519, bytes)
+    }
+
+    /// Create a key pair from secret key bytes of an explicitly-known
+    /// `key_type`. `bytes` is consumed directly into the underlying
+    /// signing key with no intermediate copy on our side to zero; callers
+    /// holding the bytes in their own buffer are responsible for zeroing
+    /// it themselves once this returns (e.g. via [`zeroize::Zeroize`]).
+    pub fn from_typed_secret_key_bytes(key_type: KeyType, bytes: &[u8]) -> Result<Self, crate::SDUPIError> {
+        let inner = match key_type {
+            KeyType::Ed25519 => {
+                let secret_key = SecretKey::from_bytes(bytes)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid secret key: {}", e)))?;
+
+                let public_key = (&secret_key).into();
+                KeyPairInner::Ed25519(Keypair {
+                    secret: secret_key,
+                    public: public_key,
+                })
+            }
+            KeyType::Secp256k1 => {
+                let signing_key = SigningKey::from_slice(bytes)
+                    .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid secret key: {}", e)))?;
+                KeyPairInner::Secp256k1(signing_key)
+            }
         };
-        
+
         Ok(Self { inner })
     }
-    
+
     /// Get the public key
     pub fn public_key(&self) -> PublicKey {
-        PublicKey {
-            inner: self.inner.public,
-        }
+        let inner = match &self.inner {
+            KeyPairInner::Ed25519(keypair) => PublicKeyInner::Ed25519(keypair.public),
+            KeyPairInner::Secp256k1(signing_key) => PublicKeyInner::Secp256k1(signing_key.verifying_key().clone()),
+        };
+        PublicKey { inner }
     }
-    
-    /// Get the secret key bytes
-    pub fn secret_key_bytes(&self) -> [u8; 32] {
-        self.inner.secret.to_bytes()
+
+    /// Get the secret key bytes, wrapped in a guard that zeroes them on
+    /// drop. Call [`SecretKeyBytes::expose_secret`] to read the bytes.
+    pub fn secret_key_bytes(&self) -> SecretKeyBytes {
+        let bytes = match &self.inner {
+            KeyPairInner::Ed25519(keypair) => keypair.secret.to_bytes(),
+            KeyPairInner::Secp256k1(signing_key) => signing_key.to_bytes().into(),
+        };
+        SecretKeyBytes(bytes)
     }
-    
+
     /// Sign a message
     pub fn sign(&self, message: &[u8]) -> Vec<u8> {
-        let signature = self.inner.sign(message);
-        signature.to_bytes().to_vec()
+        match &self.inner {
+            KeyPairInner::Ed25519(keypair) => keypair.sign(message).to_bytes().to_vec(),
+            KeyPairInner::Secp256k1(signing_key) => {
+                let signature: Secp256k1Signature = signing_key.sign(message);
+                signature.to_bytes().to_vec()
+            }
+        }
     }
-    
+
     /// Sign a transaction
     pub fn sign_transaction(&self, transaction_hash: &[u8]) -> Vec<u8> {
         self.sign(transaction_hash)
     }
+
+    /// Sign `message` with a recoverable secp256k1 signature: 64 bytes of
+    /// (r, s) followed by a 1-byte recovery id `v` normalized to {0, 1}.
+    /// Pair with [`utils::recover`] to recover the public key from the
+    /// signature alone, the way account-based chains recover a sender
+    /// address without shipping the public key with every transaction.
+    /// Only supported for `KeyType::Secp256k1` key pairs.
+    pub fn sign_recoverable(&self, message: &[u8]) -> Result<[u8; 65], crate::SDUPIError> {
+        let signing_key = match &self.inner {
+            KeyPairInner::Secp256k1(signing_key) => signing_key,
+            KeyPairInner::Ed25519(_) => {
+                return Err(crate::SDUPIError::Crypto(
+                    "Recoverable signatures are only supported for secp256k1 key pairs".to_string(),
+                ))
+            }
+        };
+
+        let (signature, recovery_id): (Secp256k1Signature, RecoveryId) = signing_key
+            .sign_recoverable(message)
+            .map_err(|e| crate::SDUPIError::Crypto(format!("secp256k1 signing failed: {}", e)))?;
+
+        let mut bytes = [0u8; 65];
+        bytes[..64].copy_from_slice(&signature.to_bytes());
+        bytes[64] = recovery_id.to_byte();
+        Ok(bytes)
+    }
+
+    /// Sign an already-computed 32-byte digest directly, without re-hashing
+    /// it inside `sign`. This is how web3-style libraries pass a
+    /// keccak256-hashed message into secp256k1 signing; pair with
+    /// [`utils::keccak256`] and [`utils::eth_sign`] to produce
+    /// Ethereum-compatible signatures. Only supported for
+    /// `KeyType::Secp256k1` key pairs.
+    pub fn sign_prehashed(&self, digest: &[u8; 32]) -> Result<Vec<u8>, crate::SDUPIError> {
+        let signing_key = match &self.inner {
+            KeyPairInner::Secp256k1(signing_key) => signing_key,
+            KeyPairInner::Ed25519(_) => {
+                return Err(crate::SDUPIError::Crypto(
+                    "Pre-hashed signing is only supported for secp256k1 key pairs".to_string(),
+                ))
+            }
+        };
+
+        let signature: Secp256k1Signature = signing_key
+            .sign_prehash(digest)
+            .map_err(|e| crate::SDUPIError::Crypto(format!("secp256k1 signing failed: {}", e)))?;
+
+        Ok(signature.to_bytes().to_vec())
+    }
 }
 
 impl fmt::Display for PublicKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", hex::encode(self.to_bytes()))
+        write!(f, "{}", self.to_did_key())
     }
 }
 
@@ -98,25 +455,108 @@ impl fmt::Display for KeyPair {
     }
 }
 
+/// A hash algorithm `utils::hash` can dispatch to. Callers that store a
+/// hash alongside its [`HashAlgorithm`] (see [`utils::digest_and_prefix`])
+/// keep it verifiable even if the default algorithm changes later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Strongest-to-weakest negotiation order, the same approach TUF uses
+    /// to pick the strongest hash algorithm both sides support.
+    pub const PREFERENCE_ORDER: [HashAlgorithm; 2] = [HashAlgorithm::Sha512, HashAlgorithm::Sha256];
+}
+
+impl fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HashAlgorithm::Sha256 => write!(f, "sha256"),
+            HashAlgorithm::Sha512 => write!(f, "sha512"),
+        }
+    }
+}
+
 /// Cryptographic utilities
 pub mod utils {
     use super::*;
-    use sha2::{Sha256, Digest};
-    
+    use sha2::{Sha256, Sha512, Digest};
+    use sha3::Keccak256;
+
     /// Hash data using SHA-256
     pub fn sha256(data: &[u8]) -> Vec<u8> {
         let mut hasher = Sha256::new();
         hasher.update(data);
         hasher.finalize().to_vec()
     }
-    
+
+    /// Hash data using SHA-512
+    pub fn sha512(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha512::new();
+        hasher.update(data);
+        hasher.finalize().to_vec()
+    }
+
+    /// Hashes `data` with the given algorithm.
+    pub fn hash(algorithm: HashAlgorithm, data: &[u8]) -> Vec<u8> {
+        match algorithm {
+            HashAlgorithm::Sha256 => sha256(data),
+            HashAlgorithm::Sha512 => sha512(data),
+        }
+    }
+
+    /// Hashes `data` with `algorithm` and pairs the result with the
+    /// algorithm that produced it, so the hash can be stored and later
+    /// re-verified with [`verify_hash`] without assuming which algorithm
+    /// (or digest length) was used.
+    pub fn digest_and_prefix(algorithm: HashAlgorithm, data: &[u8]) -> (HashAlgorithm, Vec<u8>) {
+        (algorithm, hash(algorithm, data))
+    }
+
+    /// Verifies that hashing `data` with `algorithm` reproduces `expected`.
+    /// This is how transaction-hash verification should work once a hash
+    /// carries its own algorithm tag, rather than assuming every hash is
+    /// 32-byte SHA-256.
+    pub fn verify_hash(algorithm: HashAlgorithm, data: &[u8], expected: &[u8]) -> bool {
+        hash(algorithm, data) == expected
+    }
+
+    /// Hash data using keccak256, the hash EVM chains use everywhere from
+    /// addresses to transaction signing.
+    pub fn keccak256(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+
+    /// Hashes `message` the way Ethereum's `personal_sign` does: prepends
+    /// the `"\x19Ethereum Signed Message:\n" + len(message)` prefix before
+    /// keccak-hashing, so the result can be fed into
+    /// [`KeyPair::sign_prehashed`] to produce signatures dApps can verify
+    /// with standard web3 tooling.
+    pub fn eth_sign_hash(message: &[u8]) -> [u8; 32] {
+        let mut framed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+        framed.extend_from_slice(message);
+        keccak256(&framed)
+    }
+
+    /// Signs `message` with the Ethereum `personal_sign` convention:
+    /// keccak256-hash it with the `"\x19Ethereum Signed Message:\n"` prefix,
+    /// then sign the digest directly with `keypair`. Only supported for
+    /// `KeyType::Secp256k1` key pairs.
+    pub fn eth_sign(keypair: &KeyPair, message: &[u8]) -> Result<Vec<u8>, crate::SDUPIError> {
+        keypair.sign_prehashed(&eth_sign_hash(message))
+    }
+
     /// Generate a random nonce
     pub fn random_nonce() -> [u8; 32] {
         let mut nonce = [0u8; 32];
         OsRng.fill(&mut nonce);
         nonce
     }
-    
+
     /// Verify transaction signature
     pub fn verify_transaction_signature(
         public_key: &PublicKey,
@@ -125,50 +565,364 @@ pub mod utils {
     ) -> Result<(), crate::SDUPIError> {
         public_key.verify(transaction_hash, signature)
     }
+
+    /// Recovers the secp256k1 public key that produced `signature_65` (a
+    /// 64-byte (r, s) signature followed by a 1-byte recovery id `v`, as
+    /// produced by [`KeyPair::sign_recoverable`]) over `message_hash`, the
+    /// SHA-256 digest of the signed message. This is the same trick
+    /// account-based chains use to recover the sender straight from a
+    /// transaction signature, removing the need to ship the public key
+    /// alongside every transaction.
+    pub fn recover(message_hash: &[u8], signature_65: &[u8; 65]) -> Result<PublicKey, crate::SDUPIError> {
+        let recovery_id = RecoveryId::from_byte(signature_65[64])
+            .ok_or_else(|| crate::SDUPIError::Crypto(format!("Invalid recovery id: {}", signature_65[64])))?;
+        let signature = Secp256k1Signature::from_slice(&signature_65[..64])
+            .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid secp256k1 signature: {}", e)))?;
+
+        let verifying_key = VerifyingKey::recover_from_prehash(message_hash, &signature, recovery_id)
+            .map_err(|e| crate::SDUPIError::Crypto(format!("Public key recovery failed: {}", e)))?;
+
+        Ok(PublicKey { inner: PublicKeyInner::Secp256k1(verifying_key) })
+    }
+
+    /// Verifies many `(public_key, message, signature)` entries at once.
+    /// When every entry is an ed25519 key, this collapses the per-signature
+    /// point checks into a single multi-scalar multiplication via
+    /// `ed25519_dalek`'s batch verifier -- substantially faster than
+    /// verifying each signature individually, which matters when a
+    /// validator has thousands of transaction signatures to check per
+    /// block. Mixed-type batches (or a failed fast-path batch) fall back to
+    /// per-entry verification so a failure can report exactly which
+    /// entries didn't verify.
+    pub fn verify_batch(entries: &[(PublicKey, Vec<u8>, Vec<u8>)]) -> Result<(), crate::SDUPIError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        if entries.iter().all(|(key, _, _)| key.key_type() == KeyType::Ed25519)
+            && verify_ed25519_batch(entries).is_ok()
+        {
+            return Ok(());
+        }
+
+        let failing_indices: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (key, message, signature))| {
+                if key.verify(message, signature).is_ok() {
+                    None
+                } else {
+                    Some(i)
+                }
+            })
+            .collect();
+
+        if failing_indices.is_empty() {
+            Ok(())
+        } else {
+            Err(crate::SDUPIError::Crypto(format!(
+                "batch verification failed: {} of {} signatures invalid (failing indices: {:?})",
+                failing_indices.len(),
+                entries.len(),
+                failing_indices
+            )))
+        }
+    }
+}
+
+/// Runs `ed25519_dalek`'s batch verifier over `entries`, all of which must
+/// be ed25519 keys. Returns `Err` on any invalid signature without
+/// indicating which one -- the caller falls back to per-entry verification
+/// to report that.
+fn verify_ed25519_batch(entries: &[(PublicKey, Vec<u8>, Vec<u8>)]) -> Result<(), crate::SDUPIError> {
+    let messages: Vec<&[u8]> = entries.iter().map(|(_, message, _)| message.as_slice()).collect();
+
+    let signatures = entries
+        .iter()
+        .map(|(_, _, signature)| {
+            Ed25519Signature::from_bytes(signature)
+                .map_err(|e| crate::SDUPIError::Crypto(format!("Invalid signature: {}", e)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let public_keys: Vec<Ed25519PublicKey> = entries
+        .iter()
+        .map(|(key, _, _)| match &key.inner {
+            PublicKeyInner::Ed25519(inner) => *inner,
+            PublicKeyInner::Secp256k1(_) => unreachable!("caller only passes ed25519 entries"),
+        })
+        .collect();
+
+    ed25519_dalek::verify_batch(&messages, &signatures, &public_keys)
+        .map_err(|e| crate::SDUPIError::Crypto(format!("Batch signature verification failed: {}", e)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_keypair_generation() {
         let keypair = KeyPair::generate();
         let public_key = keypair.public_key();
-        
+
         assert_eq!(public_key.to_bytes().len(), 32);
     }
-    
+
     #[test]
     fn test_message_signing_and_verification() {
         let keypair = KeyPair::generate();
         let message = b"Hello, SDUPI!";
-        
+
         let signature = keypair.sign(message);
         let public_key = keypair.public_key();
-        
+
         assert!(public_key.verify(message, &signature).is_ok());
     }
-    
+
     #[test]
     fn test_invalid_signature_rejection() {
         let keypair = KeyPair::generate();
         let public_key = keypair.public_key();
         let message = b"Hello, SDUPI!";
-        
+
         let invalid_signature = vec![0u8; 64];
-        
+
         assert!(public_key.verify(message, &invalid_signature).is_err());
     }
-    
+
     #[test]
     fn test_keypair_serialization() {
         let keypair = KeyPair::generate();
         let public_key = keypair.public_key();
-        
+
         let serialized = serde_json::to_string(&public_key).unwrap();
         let deserialized: PublicKey = serde_json::from_str(&serialized).unwrap();
-        
+
         assert_eq!(public_key, deserialized);
     }
+
+    #[test]
+    fn test_generate_secp256k1_keypair() {
+        let keypair = KeyPair::generate_with_type(KeyType::Secp256k1);
+        let public_key = keypair.public_key();
+
+        assert_eq!(public_key.key_type(), KeyType::Secp256k1);
+        assert_eq!(public_key.to_bytes().len(), 33);
+    }
+
+    #[test]
+    fn test_secp256k1_signing_and_verification() {
+        let keypair = KeyPair::generate_with_type(KeyType::Secp256k1);
+        let message = b"Hello, SDUPI!";
+
+        let signature = keypair.sign(message);
+        let public_key = keypair.public_key();
+
+        assert!(public_key.verify(message, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_cross_type_signature_is_rejected() {
+        let ed25519_keypair = KeyPair::generate();
+        let secp256k1_keypair = KeyPair::generate_with_type(KeyType::Secp256k1);
+        let message = b"Hello, SDUPI!";
+
+        let signature = ed25519_keypair.sign(message);
+
+        assert!(secp256k1_keypair.public_key().verify(message, &signature).is_err());
+    }
+
+    #[test]
+    fn test_prefixed_bytes_round_trip_preserves_key_type() {
+        let ed25519_public_key = KeyPair::generate().public_key();
+        let secp256k1_public_key = KeyPair::generate_with_type(KeyType::Secp256k1).public_key();
+
+        let restored_ed25519 = PublicKey::from_prefixed_bytes(&ed25519_public_key.to_prefixed_bytes()).unwrap();
+        let restored_secp256k1 = PublicKey::from_prefixed_bytes(&secp256k1_public_key.to_prefixed_bytes()).unwrap();
+
+        assert_eq!(ed25519_public_key, restored_ed25519);
+        assert_eq!(secp256k1_public_key, restored_secp256k1);
+        assert_eq!(restored_secp256k1.key_type(), KeyType::Secp256k1);
+    }
+
+    #[test]
+    fn test_key_type_display_and_from_str_round_trip() {
+        assert_eq!(KeyType::from_str("ed25519").unwrap(), KeyType::Ed25519);
+        assert_eq!(KeyType::from_str("secp256k1").unwrap(), KeyType::Secp256k1);
+        assert_eq!(KeyType::Ed25519.to_string(), "ed25519");
+        assert_eq!(KeyType::Secp256k1.to_string(), "secp256k1");
+        assert!(KeyType::from_str("bogus").is_err());
+    }
+
+    #[test]
+    fn test_sign_recoverable_and_recover_round_trip() {
+        let keypair = KeyPair::generate_with_type(KeyType::Secp256k1);
+        let message = b"Hello, SDUPI!";
+
+        let signature = keypair.sign_recoverable(message).unwrap();
+        let message_hash = utils::sha256(message);
+        let recovered = utils::recover(&message_hash, &signature).unwrap();
+
+        assert_eq!(recovered, keypair.public_key());
+    }
+
+    #[test]
+    fn test_sign_recoverable_rejects_ed25519_keypairs() {
+        let keypair = KeyPair::generate();
+        assert!(keypair.sign_recoverable(b"Hello, SDUPI!").is_err());
+    }
+
+    #[test]
+    fn test_keccak256_matches_known_test_vector() {
+        let digest = utils::keccak256(b"");
+        assert_eq!(hex::encode(digest), "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+    }
+
+    #[test]
+    fn test_eth_sign_produces_a_signature_verifiable_against_its_digest() {
+        use k256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        let keypair = KeyPair::generate_with_type(KeyType::Secp256k1);
+        let message = b"Hello, SDUPI!";
+
+        let signature_bytes = utils::eth_sign(&keypair, message).unwrap();
+        let signature = Secp256k1Signature::from_slice(&signature_bytes).unwrap();
+        let digest = utils::eth_sign_hash(message);
+
+        let verifying_key = match &keypair.public_key().inner {
+            PublicKeyInner::Secp256k1(key) => key.clone(),
+            PublicKeyInner::Ed25519(_) => unreachable!(),
+        };
+        assert!(verifying_key.verify_prehash(&digest, &signature).is_ok());
+    }
+
+    #[test]
+    fn test_sign_prehashed_rejects_ed25519_keypairs() {
+        let keypair = KeyPair::generate();
+        assert!(keypair.sign_prehashed(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_did_key_round_trips_an_ed25519_public_key() {
+        let public_key = KeyPair::generate().public_key();
+
+        let did_key = public_key.to_did_key();
+        assert!(did_key.starts_with("did:key:z"));
+
+        let restored = PublicKey::from_did_key(&did_key).unwrap();
+        assert_eq!(public_key, restored);
+        assert_eq!(restored.key_type(), KeyType::Ed25519);
+    }
+
+    #[test]
+    fn test_did_key_round_trips_a_secp256k1_public_key() {
+        let public_key = KeyPair::generate_with_type(KeyType::Secp256k1).public_key();
+
+        let did_key = public_key.to_did_key();
+        let restored = PublicKey::from_did_key(&did_key).unwrap();
+
+        assert_eq!(public_key, restored);
+        assert_eq!(restored.key_type(), KeyType::Secp256k1);
+    }
+
+    #[test]
+    fn test_from_did_key_rejects_unknown_codec_prefix() {
+        let public_key = KeyPair::generate().public_key();
+        let mut bytes = Vec::new();
+        write_varint(0x1234, &mut bytes);
+        bytes.extend(public_key.to_bytes());
+        let did_key = format!("did:key:z{}", bs58::encode(bytes).into_string());
+
+        assert!(PublicKey::from_did_key(&did_key).is_err());
+    }
+
+    #[test]
+    fn test_from_did_key_rejects_malformed_prefix() {
+        assert!(PublicKey::from_did_key("not-a-did-key").is_err());
+    }
+
+    #[test]
+    fn test_secret_key_bytes_zeroizes_its_contents_on_drop() {
+        let keypair = KeyPair::generate();
+
+        let ptr = {
+            let secret_bytes = keypair.secret_key_bytes();
+            assert_ne!(secret_bytes.expose_secret(), &[0u8; 32]);
+            secret_bytes.0.as_ptr()
+            // `secret_bytes` drops here, which should zero its buffer.
+        };
+
+        let after_drop = unsafe { std::slice::from_raw_parts(ptr, 32) };
+        assert_eq!(after_drop, &[0u8; 32][..]);
+    }
+
+    #[test]
+    fn test_verify_batch_accepts_all_valid_signatures() {
+        let message = b"Hello, SDUPI!";
+        let entries: Vec<(PublicKey, Vec<u8>, Vec<u8>)> = (0..8)
+            .map(|_| {
+                let keypair = KeyPair::generate();
+                let signature = keypair.sign(message);
+                (keypair.public_key(), message.to_vec(), signature)
+            })
+            .collect();
+
+        assert!(utils::verify_batch(&entries).is_ok());
+    }
+
+    #[test]
+    fn test_verify_batch_rejects_one_corrupted_signature() {
+        let message = b"Hello, SDUPI!";
+        let mut entries: Vec<(PublicKey, Vec<u8>, Vec<u8>)> = (0..8)
+            .map(|_| {
+                let keypair = KeyPair::generate();
+                let signature = keypair.sign(message);
+                (keypair.public_key(), message.to_vec(), signature)
+            })
+            .collect();
+
+        let corrupted_index = 3;
+        entries[corrupted_index].2[0] ^= 0xff;
+
+        let result = utils::verify_batch(&entries);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains(&corrupted_index.to_string()));
+    }
+
+    #[test]
+    fn test_verify_batch_is_empty_ok() {
+        assert!(utils::verify_batch(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_hash_dispatches_to_the_requested_algorithm() {
+        let data = b"sdupi";
+        assert_eq!(utils::hash(HashAlgorithm::Sha256, data), utils::sha256(data));
+        assert_eq!(utils::hash(HashAlgorithm::Sha512, data), utils::sha512(data));
+        assert_ne!(utils::sha256(data), utils::sha512(data));
+    }
+
+    #[test]
+    fn test_digest_and_prefix_round_trips_through_verify_hash() {
+        let data = b"preference order matters";
+        for algorithm in HashAlgorithm::PREFERENCE_ORDER {
+            let (tag, digest) = utils::digest_and_prefix(algorithm, data);
+            assert_eq!(tag, algorithm);
+            assert!(utils::verify_hash(tag, data, &digest));
+        }
+    }
+
+    #[test]
+    fn test_verify_hash_rejects_mismatched_algorithm() {
+        let data = b"algorithm confusion";
+        let (_, sha256_digest) = utils::digest_and_prefix(HashAlgorithm::Sha256, data);
+        assert!(!utils::verify_hash(HashAlgorithm::Sha512, data, &sha256_digest));
+    }
+
+    #[test]
+    fn test_hash_algorithm_display() {
+        assert_eq!(HashAlgorithm::Sha256.to_string(), "sha256");
+        assert_eq!(HashAlgorithm::Sha512.to_string(), "sha512");
+    }
 }
@@ -1,13 +1,37 @@
 use std::collections::{HashMap, HashSet, VecDeque, BTreeMap};
 use std::sync::{Arc, RwLock, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
+use std::path::PathBuf;
 use uuid::Uuid;
-use tokio::sync::{mpsc, Semaphore};
+use tokio::sync::{mpsc, oneshot, Semaphore};
 use rayon::prelude::*;
+use serde::{Serialize, Deserialize};
 use crate::transaction::{Transaction, TransactionStatus};
 use crate::crypto::PublicKey;
 use crate::SDUPIError;
 
+/// Cap on how many `BatchTraceEntry` records the in-memory trace log keeps
+/// before dropping the oldest, so tracing a long-running node can't leak.
+const TRACE_LOG_CAPACITY: usize = 10_000;
+
+/// Alias kept for call sites (e.g. `network.rs`) that predate the
+/// `AdvancedDAGLedger` rename; the "Legacy compatibility" impl block below
+/// already gives it the old `DAGLedger` surface.
+pub type DAGLedger = AdvancedDAGLedger;
+
+/// Flat cost every transaction pays regardless of shape, inspired by
+/// base-extrinsic weighting: covers the fixed overhead of validating and
+/// storing a node in the DAG.
+const BASE_TRANSACTION_WEIGHT: u64 = 100;
+/// Marginal cost per signature attached to a transaction.
+const PER_SIGNATURE_WEIGHT: u64 = 50;
+/// Marginal cost per byte of signature + ZK-STARK proof payload.
+const PER_PAYLOAD_BYTE_WEIGHT: u64 = 1;
+/// Marginal cost per referenced parent, for the extra DAG-traversal work
+/// each parent link costs to validate.
+const PER_PARENT_WEIGHT: u64 = 25;
+
 /// Advanced DAG configuration for ultra-high performance
 #[derive(Debug, Clone)]
 pub struct AdvancedDAGConfig {
@@ -34,9 +58,170 @@ pub struct AdvancedDAGConfig {
     
     /// Advanced conflict resolution
     pub conflict_resolution: AdvancedConflictResolution,
-    
+
     /// Performance optimization flags
     pub optimizations: DAGOptimizations,
+
+    /// How thoroughly incoming transactions are validated
+    pub verification_level: VerificationLevel,
+
+    /// Transactions below this fee are rejected before allocation, keeping
+    /// dust out of `pending_queue`
+    pub min_effective_fee: u64,
+
+    /// A replacement for an already-pending transaction from the same
+    /// sender must beat its fee by at least this fraction (e.g. `0.1` for a
+    /// standard 10% fee-bump) to evict and replace it
+    pub fee_bump_factor: f64,
+
+    /// How long a node may wait in the orphan buffer for its missing
+    /// parents before it is evicted, mirroring the pacemaker's
+    /// leader-timeout reset so a stuck dependency never wedges throughput
+    pub orphan_timeout: Duration,
+
+    /// Cumulative descendant weight a confirmed transaction must accumulate
+    /// before it (and its ancestors) are rooted into a checkpoint
+    pub rooting_weight_threshold: u64,
+
+    /// Upper bound on how many transactions `ready_transactions` returns in
+    /// one call, so a networking layer's relay batches stay a fixed cost
+    /// regardless of how deep `pending_queue` has backed up
+    pub max_transactions_to_propagate: usize,
+
+    /// Upper bound on a `TransactionBatch`'s total metered weight.
+    /// `process_transactions_parallel` packs batches against this instead
+    /// of `batch_size`, so a batch of a few expensive transactions costs
+    /// the same to process as a batch of many cheap ones. Also the scale
+    /// against which the congestion multiplier in
+    /// `calculate_priority_score` is measured.
+    pub max_batch_weight: u64,
+
+    /// When set, every batch `process_transactions_parallel` forms is
+    /// recorded -- ordered transaction ids, their admission-time priority
+    /// scores and predicted conflict probabilities, the worker id and the
+    /// resulting `ProcessingResult` -- to a bounded in-memory trace log
+    /// (and, if `trace_log_path` is set, appended to disk) for later replay
+    pub enable_tracing: bool,
+
+    /// Where the batch trace is persisted as newline-delimited JSON, so
+    /// `replay_trace` can read it back. Ignored unless `enable_tracing`.
+    pub trace_log_path: Option<PathBuf>,
+
+    /// How long a rooted transaction must sit settled before
+    /// `prune_confirmed` considers it old enough to evict from the hot
+    /// in-memory maps
+    pub prune_after: Duration,
+
+    /// Cumulative descendant weight a rooted transaction must additionally
+    /// accumulate, on top of clearing `rooting_weight_threshold`, before
+    /// `prune_confirmed` considers it buried deep enough to evict
+    pub prune_confirmation_depth: u64,
+
+    /// Upper bound on nodes evicted per `prune_confirmed` call, so working
+    /// off a deep backlog of settled history never stalls
+    /// `process_pending_transactions`
+    pub prune_batch_size: usize,
+}
+
+/// How thoroughly a node validates transactions before admitting them to
+/// the DAG. Lets lightweight edge/mobile participants sync and relay tips
+/// without paying the cost of full-node validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationLevel {
+    /// Validate structure and vertex signature, and require a ZK-STARK
+    /// proof to be attached; the proof's contents are not verified, and a
+    /// missing parent parks the node in the orphan buffer instead.
+    Full,
+    /// Trust the transaction payload, but still verify the vertex signature
+    /// and require an attached ZK-STARK proof, enough to follow the chain tip.
+    Header,
+    /// No validation; only track tips for relay.
+    None,
+}
+
+impl Default for VerificationLevel {
+    fn default() -> Self {
+        VerificationLevel::Full
+    }
+}
+
+impl std::str::FromStr for VerificationLevel {
+    type Err = SDUPIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(VerificationLevel::Full),
+            "header" => Ok(VerificationLevel::Header),
+            "none" => Ok(VerificationLevel::None),
+            other => Err(SDUPIError::Consensus(format!("Unknown verification level: {}", other))),
+        }
+    }
+}
+
+/// A transaction's lifecycle state, bank-style: it opens as `Pending`,
+/// advances through `Validated` and `Confirmed`, and finally becomes
+/// `Rooted` once enough descendant weight has built on top of it to make
+/// reorganizing it impractical. `Unknown` covers ids this node has never
+/// seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    Pending,
+    Validated,
+    Confirmed,
+    Rooted,
+    Unknown,
+}
+
+/// An immutable snapshot of the rooted frontier at the moment it was taken,
+/// chained to the checkpoint before it so a light client can sync forward
+/// from the last checkpoint it trusts instead of replaying the whole DAG.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    pub id: Uuid,
+    pub sequence: u64,
+    /// Ids rooted for the first time as of this checkpoint
+    pub rooted_frontier: Vec<Uuid>,
+    /// Previous checkpoint's id, or `None` for the first checkpoint
+    pub parent: Option<Uuid>,
+    pub created_at: Instant,
+}
+
+/// Rolling Merkle-chained digest over pruned nodes: each pruned node folds
+/// its `(id, payload_hash)` pair into the running root as
+/// `root' = sha256(root || id || payload_hash)`, so a compact digest of
+/// everything evicted so far can still be verified without keeping the
+/// original nodes around.
+#[derive(Debug, Clone)]
+struct PruneDigest {
+    root: [u8; 32],
+    count: u64,
+}
+
+impl PruneDigest {
+    fn new() -> Self {
+        Self { root: [0u8; 32], count: 0 }
+    }
+
+    fn fold(&mut self, id: &Uuid, payload_hash: &[u8]) {
+        let mut preimage = Vec::with_capacity(32 + 16 + payload_hash.len());
+        preimage.extend_from_slice(&self.root);
+        preimage.extend_from_slice(id.as_bytes());
+        preimage.extend_from_slice(payload_hash);
+        let digest = crate::crypto::utils::sha256(&preimage);
+        self.root.copy_from_slice(&digest);
+        self.count += 1;
+    }
+}
+
+/// A compact, on-demand summary of everything `prune_confirmed` has
+/// evicted so far: the Merkle-chained digest root plus how many nodes it
+/// has folded in, returned by `AdvancedDAGLedger::create_snapshot`.
+#[derive(Debug, Clone)]
+pub struct PruneSnapshot {
+    /// Hex-encoded rolling digest root over every pruned node
+    pub root: String,
+    /// Total nodes folded into `root` so far
+    pub pruned_count: u64,
 }
 
 /// Advanced conflict resolution configuration
@@ -108,6 +293,21 @@ impl Default for AdvancedDAGConfig {
             enable_zero_copy: true,
             conflict_resolution: AdvancedConflictResolution::default(),
             optimizations: DAGOptimizations::default(),
+            verification_level: VerificationLevel::default(),
+            min_effective_fee: 1,
+            fee_bump_factor: 0.1, // standard 10% fee bump to replace a pending tx
+            orphan_timeout: Duration::from_secs(30),
+            // A handful of confirmed descendants' worth of metered weight
+            rooting_weight_threshold: 500,
+            max_transactions_to_propagate: 1_000,
+            max_batch_weight: 5_000_000,
+            enable_tracing: false,
+            trace_log_path: None,
+            prune_after: Duration::from_secs(3600),
+            // Several multiples of the rooting threshold's worth of
+            // descendant weight, so pruning lags well behind rooting
+            prune_confirmation_depth: 2_000,
+            prune_batch_size: 256,
         }
     }
 }
@@ -175,6 +375,19 @@ pub struct NodePerformanceMetrics {
     
     /// Validation efficiency
     pub validation_efficiency: f64,
+
+    /// Metered execution cost: base cost plus marginal costs for signature
+    /// count, payload size and referenced parents
+    pub weight: u64,
+
+    /// Fee earned per unit of `weight`, for comparing resource efficiency
+    /// across transactions of different shapes rather than just raw fee
+    pub fee_per_weight: f64,
+
+    /// Conflict probability predicted by the AI conflict predictor at the
+    /// moment this transaction was admitted, kept around so batch traces
+    /// can record what the scheduler knew at admission time
+    pub conflict_probability: f64,
 }
 
 impl Default for NodePerformanceMetrics {
@@ -184,6 +397,9 @@ impl Default for NodePerformanceMetrics {
             memory_usage: 0,
             cache_hit_rate: 0.0,
             validation_efficiency: 1.0,
+            weight: 0,
+            fee_per_weight: 0.0,
+            conflict_probability: 0.0,
         }
     }
 }
@@ -222,6 +438,123 @@ pub struct AdvancedDAGLedger {
     
     /// AI conflict predictor
     ai_conflict_predictor: Arc<RwLock<AIConflictPredictor>>,
+
+    /// Nodes parked because one or more referenced parents haven't arrived yet
+    orphan_nodes: Arc<RwLock<HashMap<Uuid, OrphanEntry>>>,
+
+    /// Maps a missing parent id to the orphan nodes waiting on it
+    orphan_waiters: Arc<RwLock<HashMap<Uuid, Vec<Uuid>>>>,
+
+    /// Transactions rooted into a checkpoint -- final, and no longer
+    /// eligible for conflict resolution or eviction
+    rooted_transactions: Arc<RwLock<HashSet<Uuid>>>,
+
+    /// Chain of checkpoints taken as the rooted frontier advances
+    checkpoints: Arc<RwLock<Vec<Checkpoint>>>,
+
+    /// Ids already handed out by `ready_transactions`, so a networking
+    /// layer doesn't re-gossip the same pending transaction every round
+    propagated: Arc<RwLock<HashSet<Uuid>>>,
+
+    /// Bounded in-memory record of batches processed while tracing is
+    /// enabled, for diagnostics without needing `trace_log_path` set
+    trace_log: Arc<RwLock<VecDeque<BatchTraceEntry>>>,
+
+    /// Dedicated unbounded channel feeding the background trace writer, so
+    /// recording a trace entry never blocks the processing hot path.
+    /// `None` when `config.enable_tracing` is false.
+    trace_sender: Option<mpsc::UnboundedSender<BatchTraceEntry>>,
+
+    /// Exponential-bucket histogram of batch processing latency, backing
+    /// the p50/p95/p99 figures in `AdvancedDAGStatistics`
+    latency_histogram: Arc<LatencyHistogram>,
+
+    /// Rooted ids not yet pruned, oldest-rooted-first, so `prune_confirmed`
+    /// can do bounded incremental work each call instead of rescanning all
+    /// of `rooted_transactions` every time
+    prune_queue: Arc<RwLock<VecDeque<Uuid>>>,
+
+    /// Rolling digest folding in every node `prune_confirmed` has evicted
+    prune_digest: Arc<RwLock<PruneDigest>>,
+}
+
+/// An `AdvancedDAGNode` parked in the orphan buffer, waiting on the parents
+/// listed in `missing_parents` to arrive before it can be admitted.
+struct OrphanEntry {
+    node: AdvancedDAGNode,
+    missing_parents: HashSet<Uuid>,
+    parked_at: Instant,
+}
+
+/// Number of exponential buckets kept by `LatencyHistogram`, covering
+/// durations up to `2^LATENCY_HISTOGRAM_BUCKETS` microseconds (~17 minutes)
+/// -- far past anything a single batch should ever take.
+const LATENCY_HISTOGRAM_BUCKETS: usize = 30;
+
+/// Lock-free latency histogram: bucket `i` counts durations in the range
+/// `[2^i, 2^(i+1))` microseconds. This gives accurate tail percentiles
+/// (p50/p95/p99) without storing every sample, at the cost of reporting
+/// each percentile as the geometric midpoint of its bucket rather than an
+/// exact value.
+#[derive(Debug)]
+struct LatencyHistogram {
+    buckets: [AtomicU64; LATENCY_HISTOGRAM_BUCKETS],
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+
+    fn record(&self, duration: Duration) {
+        let micros = duration.as_micros().max(1) as u64;
+        let bucket = (63 - micros.leading_zeros() as usize).min(LATENCY_HISTOGRAM_BUCKETS - 1);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Representative duration for bucket `i`: the geometric midpoint of
+    /// `[2^i, 2^(i+1))`.
+    fn bucket_midpoint(index: usize) -> Duration {
+        Duration::from_micros((2f64.powi(index as i32) * std::f64::consts::SQRT_2) as u64)
+    }
+
+    fn percentile(&self, quantile: f64) -> Duration {
+        let counts: Vec<u64> = self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect();
+        let total: u64 = counts.iter().sum();
+        if total == 0 {
+            return Duration::from_micros(0);
+        }
+
+        let target = ((total as f64) * quantile).ceil() as u64;
+        let mut cumulative = 0u64;
+        for (i, &count) in counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return Self::bucket_midpoint(i);
+            }
+        }
+        Self::bucket_midpoint(LATENCY_HISTOGRAM_BUCKETS - 1)
+    }
+
+    fn max(&self) -> Duration {
+        self.buckets.iter()
+            .enumerate()
+            .rev()
+            .find(|(_, bucket)| bucket.load(Ordering::Relaxed) > 0)
+            .map(|(i, _)| Self::bucket_midpoint(i))
+            .unwrap_or(Duration::from_micros(0))
+    }
+
+    /// Halve every bucket so percentiles track recent throughput rather
+    /// than all-time history, emulating a rolling window without the cost
+    /// of expiring individual samples.
+    fn decay(&self) {
+        for bucket in &self.buckets {
+            let _ = bucket.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| Some(v / 2));
+        }
+    }
 }
 
 /// Memory pool for efficient allocation
@@ -328,13 +661,12 @@ impl Default for PredictiveCache {
 pub struct ProcessingWorker {
     /// Worker ID
     pub worker_id: usize,
-    
-    /// Transaction channel
-    pub tx_channel: mpsc::Sender<TransactionBatch>,
-    
-    /// Result channel
-    pub result_channel: mpsc::Receiver<ProcessingResult>,
-    
+
+    /// Batch channel. Each batch carries its own oneshot reply sender, so
+    /// the caller gets back the exact result for the exact batch it sent
+    /// even when several batches round-robin onto the same worker.
+    pub tx_channel: mpsc::Sender<(TransactionBatch, oneshot::Sender<ProcessingResult>)>,
+
     /// Worker handle
     pub handle: tokio::task::JoinHandle<()>,
 }
@@ -353,9 +685,16 @@ pub struct TransactionBatch {
     
     /// Batch size
     pub size: usize,
-    
+
     /// Priority score
     pub priority_score: f64,
+
+    /// Sum of `transactions`' metered weight
+    pub total_weight: u64,
+
+    /// Cap this batch was packed against; `total_weight` exceeds it only
+    /// when the batch is a single transaction heavier than the cap
+    pub max_batch_weight: u64,
 }
 
 /// Processing result
@@ -378,16 +717,44 @@ pub struct ProcessingResult {
 }
 
 /// Processing status
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProcessingStatus {
     /// Successfully processed
     Success,
-    
+
     /// Partially processed
     Partial,
-    
+
     /// Failed processing
     Failed,
+
+    /// Some transactions in the batch reference a parent that is no longer
+    /// in the DAG (e.g. evicted by a fee-bump replacement or pool-capacity
+    /// pressure after this transaction was admitted). Carries the ids of
+    /// the dependent transactions that need to be re-queued once their
+    /// parent reappears.
+    MissingParents(Vec<Uuid>),
+}
+
+/// A single recorded batch from `process_transactions_parallel`: everything
+/// needed to replay the same batch through the worker pipeline and confirm
+/// it produces the same `ProcessingStatus` again. Captured at batch-build
+/// time (transaction order, priority scores, conflict probabilities) and at
+/// batch-completion time (the worker id and `ProcessingResult`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchTraceEntry {
+    pub batch_id: Uuid,
+    /// Ordered transaction ids, for quick diagnostics without deserializing
+    /// the full payloads below
+    pub transaction_ids: Vec<Uuid>,
+    /// Full transactions in the same order as `transaction_ids`, so
+    /// `replay_trace` can re-admit them into a fresh ledger
+    pub transactions: Vec<Transaction>,
+    pub priority_scores: Vec<f64>,
+    pub conflict_probabilities: Vec<f64>,
+    pub worker_id: usize,
+    pub status: ProcessingStatus,
+    pub processing_time_ms: u128,
 }
 
 /// DAG performance metrics
@@ -413,6 +780,24 @@ pub struct DAGPerformanceMetrics {
     
     /// Parallel efficiency
     pub parallel_efficiency: f64,
+
+    /// Pending transactions evicted by a higher-fee replacement
+    pub fee_bump_evictions: u64,
+
+    /// Pending transactions evicted to keep the pool within
+    /// `config.memory_pool_size`, lowest fee-rate first
+    pub pool_capacity_evictions: u64,
+
+    /// Sum of metered weight across every transaction ever admitted
+    pub total_weight_processed: u64,
+
+    /// Aggregate fee earned per unit of weight across all admitted
+    /// transactions, a resource-utilization figure rather than raw TPS
+    pub fee_per_weight: f64,
+
+    /// Fully-settled nodes evicted from the hot in-memory maps by
+    /// `prune_confirmed`
+    pub pruned_transactions: u64,
 }
 
 impl Default for DAGPerformanceMetrics {
@@ -425,33 +810,55 @@ impl Default for DAGPerformanceMetrics {
             memory_utilization: 0.0,
             cache_hit_rate: 0.0,
             parallel_efficiency: 1.0,
+            fee_bump_evictions: 0,
+            pool_capacity_evictions: 0,
+            total_weight_processed: 0,
+            fee_per_weight: 0.0,
+            pruned_transactions: 0,
         }
     }
 }
 
-/// AI conflict predictor
+/// AI conflict predictor: an online logistic-regression model over a fixed
+/// feature vector, updated by SGD as transaction outcomes become known.
 pub struct AIConflictPredictor {
     /// Prediction model
     pub model: ConflictPredictionModel,
-    
+
     /// Training data
     pub training_data: Vec<ConflictData>,
-    
-    /// Prediction accuracy
+
+    /// Prediction accuracy (running fraction of correct predictions at the 0.5 threshold)
     pub accuracy: f64,
+
+    /// Ring buffer of recently-seen senders, used to derive `sender_recent_count`
+    recent_senders: VecDeque<PublicKey>,
+
+    /// Correct predictions observed so far, for the running `accuracy` average
+    correct_predictions: u64,
+
+    /// Total labeled outcomes observed so far
+    total_predictions: u64,
 }
 
-/// Conflict prediction model
+/// Conflict prediction model: a logistic-regression weight vector keyed by
+/// feature name, plus the hyperparameters needed to keep training it.
 #[derive(Debug, Clone)]
 pub struct ConflictPredictionModel {
     /// Model type
     pub model_type: String,
-    
-    /// Model parameters
+
+    /// Model parameters (weight vector `w`, keyed by feature name)
     pub parameters: HashMap<String, f64>,
-    
+
     /// Last updated
     pub last_updated: Instant,
+
+    /// SGD learning rate
+    pub learning_rate: f64,
+
+    /// Feature names, in the fixed order `feature_vector` produces them
+    pub feature_names: Vec<String>,
 }
 
 /// Conflict data for AI training
@@ -459,52 +866,136 @@ pub struct ConflictPredictionModel {
 pub struct ConflictData {
     /// Transaction pattern
     pub pattern: String,
-    
-    /// Conflict probability
+
+    /// Conflict probability predicted at observation time
     pub conflict_probability: f64,
-    
+
     /// Resolution time
     pub resolution_time: Duration,
-    
-    /// Success rate
+
+    /// Observed label: 0.0 = confirmed without conflict, 1.0 = conflict-resolved
     pub success_rate: f64,
 }
 
+/// Maximum number of recent senders kept for the `sender_recent_count` feature
+const RECENT_SENDERS_CAPACITY: usize = 256;
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
 impl AIConflictPredictor {
     /// Create new AI conflict predictor
     pub fn new() -> Self {
+        let feature_names = vec![
+            "bias".to_string(),
+            "log_fee".to_string(),
+            "log_amount".to_string(),
+            "inv_age".to_string(),
+            "sender_recent_count".to_string(),
+            "tip_overlap_ratio".to_string(),
+        ];
+        let parameters = feature_names.iter().map(|name| (name.clone(), 0.0)).collect();
+
         Self {
             model: ConflictPredictionModel {
-                model_type: "neural_network".to_string(),
-                parameters: HashMap::new(),
+                model_type: "online_logistic_regression".to_string(),
+                parameters,
                 last_updated: Instant::now(),
+                learning_rate: 0.05,
+                feature_names,
             },
             training_data: Vec::new(),
-            accuracy: 0.88,
+            accuracy: 0.5, // uninformative prior until outcomes are observed
+            recent_senders: VecDeque::with_capacity(RECENT_SENDERS_CAPACITY),
+            correct_predictions: 0,
+            total_predictions: 0,
         }
     }
-    
-    /// Predict conflict probability
-    pub fn predict_conflict(&self, transaction: &Transaction) -> f64 {
-        // Simplified AI prediction - in real implementation, this would use ML models
-        let pattern = self.extract_transaction_pattern(transaction);
-        
-        // Higher fees = lower conflict probability
-        let fee_factor = 1.0 / (transaction.fee as f64 + 1.0);
-        
-        // Newer transactions = lower conflict probability
+
+    /// Build the fixed feature vector `x` for `transaction`, in the order of
+    /// `model.feature_names`.
+    fn feature_vector(&self, transaction: &Transaction, tip_senders: &[PublicKey]) -> Vec<f64> {
         let age = chrono::Utc::now().signed_duration_since(transaction.timestamp);
-        let age_factor = 1.0 / (age.num_seconds() as f64 + 1.0);
-        
-        // Base conflict probability
-        let base_probability = 0.1;
-        
-        base_probability * fee_factor * age_factor
+        let age_secs = age.num_seconds().max(0) as f64;
+
+        let sender_recent_count = self.recent_senders.iter()
+            .filter(|sender| *sender == &transaction.sender)
+            .count() as f64;
+
+        let tip_overlap_ratio = if tip_senders.is_empty() {
+            0.0
+        } else {
+            let matching = tip_senders.iter().filter(|sender| *sender == &transaction.sender).count();
+            matching as f64 / tip_senders.len() as f64
+        };
+
+        vec![
+            1.0,                                           // bias
+            ((transaction.fee as f64) + 1.0).log10(),      // log_fee
+            ((transaction.amount as f64) + 1.0).log10(),   // log_amount
+            1.0 / (age_secs + 1.0),                        // inv_age
+            sender_recent_count,
+            tip_overlap_ratio,
+        ]
     }
-    
+
+    fn dot(&self, features: &[f64]) -> f64 {
+        self.model.feature_names.iter()
+            .zip(features.iter())
+            .map(|(name, value)| self.model.parameters.get(name).copied().unwrap_or(0.0) * value)
+            .sum()
+    }
+
+    /// Predict conflict probability `p = sigmoid(w . x)` for `transaction`,
+    /// given the senders of the DAG's current tips (used for `tip_overlap_ratio`).
+    pub fn predict_conflict(&mut self, transaction: &Transaction, tip_senders: &[PublicKey]) -> f64 {
+        let features = self.feature_vector(transaction, tip_senders);
+        let probability = sigmoid(self.dot(&features));
+
+        if self.recent_senders.len() == RECENT_SENDERS_CAPACITY {
+            self.recent_senders.pop_front();
+        }
+        self.recent_senders.push_back(transaction.sender.clone());
+
+        probability
+    }
+
+    /// Observe the true outcome for `transaction` (`label` = 0.0 for
+    /// confirmed-without-conflict, 1.0 for conflict-resolved) and perform
+    /// one SGD step `w -= lr * (p - label) * x`, clamping weights so the
+    /// model can't diverge under a burst of one-sided outcomes.
+    pub fn observe_outcome(&mut self, transaction: &Transaction, tip_senders: &[PublicKey], label: f64) {
+        let features = self.feature_vector(transaction, tip_senders);
+        let probability = sigmoid(self.dot(&features));
+        let error = probability - label;
+        let lr = self.model.learning_rate;
+
+        for (name, feature_value) in self.model.feature_names.iter().zip(features.iter()) {
+            let weight = self.model.parameters.entry(name.clone()).or_insert(0.0);
+            *weight -= lr * error * feature_value;
+            *weight = weight.clamp(-50.0, 50.0);
+        }
+        self.model.last_updated = Instant::now();
+
+        let predicted_label = if probability >= 0.5 { 1.0 } else { 0.0 };
+        self.total_predictions += 1;
+        if (predicted_label - label).abs() < f64::EPSILON {
+            self.correct_predictions += 1;
+        }
+        self.accuracy = self.correct_predictions as f64 / self.total_predictions as f64;
+
+        self.training_data.push(ConflictData {
+            pattern: self.extract_transaction_pattern(transaction),
+            conflict_probability: probability,
+            resolution_time: Duration::from_millis(0),
+            success_rate: label,
+        });
+    }
+
     /// Extract transaction pattern
     fn extract_transaction_pattern(&self, transaction: &Transaction) -> String {
-        format!("{}_{}_{}", 
+        format!("{}_{}_{}",
             transaction.sender.to_string()[..8].to_string(),
             transaction.amount,
             transaction.fee
@@ -515,6 +1006,13 @@ impl AIConflictPredictor {
 impl AdvancedDAGLedger {
     /// Create new advanced DAG ledger
     pub fn new(config: AdvancedDAGConfig) -> Self {
+        let trace_log = Arc::new(RwLock::new(VecDeque::new()));
+        let trace_sender = if config.enable_tracing {
+            Some(Self::spawn_trace_writer(trace_log.clone(), config.trace_log_path.clone()))
+        } else {
+            None
+        };
+
         let mut ledger = Self {
             transactions: Arc::new(RwLock::new(HashMap::new())),
             pending_queue: Arc::new(RwLock::new(VecDeque::new())),
@@ -527,55 +1025,120 @@ impl AdvancedDAGLedger {
             processing_workers: Vec::new(),
             performance_metrics: Arc::new(RwLock::new(DAGPerformanceMetrics::default())),
             ai_conflict_predictor: Arc::new(RwLock::new(AIConflictPredictor::new())),
+            orphan_nodes: Arc::new(RwLock::new(HashMap::new())),
+            orphan_waiters: Arc::new(RwLock::new(HashMap::new())),
+            rooted_transactions: Arc::new(RwLock::new(HashSet::new())),
+            checkpoints: Arc::new(RwLock::new(Vec::new())),
+            propagated: Arc::new(RwLock::new(HashSet::new())),
+            trace_log,
+            trace_sender,
+            latency_histogram: Arc::new(LatencyHistogram::new()),
+            prune_queue: Arc::new(RwLock::new(VecDeque::new())),
+            prune_digest: Arc::new(RwLock::new(PruneDigest::new())),
         };
-        
+
         // Initialize processing workers
         ledger.initialize_processing_workers();
-        
+
         ledger
     }
+
+    /// Spawn the background task that drains trace entries off a dedicated
+    /// unbounded channel, keeping `process_transactions_parallel`'s hot path
+    /// free of tracing I/O. Entries are folded into the bounded in-memory
+    /// `trace_log` and, if `path` is set, appended to it as
+    /// newline-delimited JSON.
+    fn spawn_trace_writer(
+        trace_log: Arc<RwLock<VecDeque<BatchTraceEntry>>>,
+        path: Option<PathBuf>,
+    ) -> mpsc::UnboundedSender<BatchTraceEntry> {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<BatchTraceEntry>();
+
+        tokio::spawn(async move {
+            while let Some(entry) = receiver.recv().await {
+                if let Some(path) = &path {
+                    if let Ok(line) = serde_json::to_string(&entry) {
+                        use std::io::Write;
+                        if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+                            let _ = writeln!(file, "{}", line);
+                        }
+                    }
+                }
+
+                if let Ok(mut log) = trace_log.write() {
+                    log.push_back(entry);
+                    while log.len() > TRACE_LOG_CAPACITY {
+                        log.pop_front();
+                    }
+                }
+            }
+        });
+
+        sender
+    }
     
     /// Initialize parallel processing workers
     fn initialize_processing_workers(&mut self) {
         for worker_id in 0..self.config.parallel_workers {
             let (tx_sender, tx_receiver) = mpsc::channel(1000);
-            let (result_sender, result_receiver) = mpsc::channel(1000);
-            
+
             let dag_ledger = self.transactions.clone();
             let handle = tokio::spawn(async move {
-                Self::processing_worker_loop(worker_id, tx_receiver, result_sender, dag_ledger).await;
+                Self::processing_worker_loop(worker_id, tx_receiver, dag_ledger).await;
             });
-            
+
             self.processing_workers.push(ProcessingWorker {
                 worker_id,
                 tx_channel: tx_sender,
-                result_channel: result_receiver,
                 handle,
             });
         }
     }
-    
+
     /// Processing worker main loop
     async fn processing_worker_loop(
         worker_id: usize,
-        mut tx_receiver: mpsc::Receiver<TransactionBatch>,
-        result_sender: mpsc::Sender<ProcessingResult>,
+        mut tx_receiver: mpsc::Receiver<(TransactionBatch, oneshot::Sender<ProcessingResult>)>,
         transactions: Arc<RwLock<HashMap<Uuid, AdvancedDAGNode>>>,
     ) {
-        while let Some(batch) = tx_receiver.recv().await {
+        while let Some((batch, reply_tx)) = tx_receiver.recv().await {
             let start_time = Instant::now();
-            
-            // Process transactions in batch
+
+            // Before validating, confirm every referenced parent is still
+            // in the DAG: it may have been evicted (fee-bump replacement,
+            // pool-capacity pressure, orphan timeout) after this
+            // transaction was admitted.
             let mut processed_transactions = Vec::new();
-            let mut processing_status = ProcessingStatus::Success;
-            
-            for transaction in &batch.transactions {
-                // Process transaction (simplified)
-                processed_transactions.push(transaction.id);
+            let mut missing_dependents = Vec::new();
+
+            {
+                let transactions_guard = transactions.read().ok();
+                for transaction in &batch.transactions {
+                    let has_missing_parent = [transaction.parent1, transaction.parent2]
+                        .into_iter()
+                        .flatten()
+                        .any(|parent_id| {
+                            transactions_guard.as_ref()
+                                .map(|t| !t.contains_key(&parent_id))
+                                .unwrap_or(false)
+                        });
+
+                    if has_missing_parent {
+                        missing_dependents.push(transaction.id);
+                    } else {
+                        processed_transactions.push(transaction.id);
+                    }
+                }
             }
-            
+
+            let processing_status = if missing_dependents.is_empty() {
+                ProcessingStatus::Success
+            } else {
+                ProcessingStatus::MissingParents(missing_dependents)
+            };
+
             let processing_time = start_time.elapsed();
-            
+
             let result = ProcessingResult {
                 batch_id: batch.batch_id,
                 status: processing_status,
@@ -584,99 +1147,564 @@ impl AdvancedDAGLedger {
                 worker_id,
             };
             
-            let _ = result_sender.send(result).await;
+            let _ = reply_tx.send(result);
         }
     }
     
+    /// Validate an incoming transaction according to `config.verification_level`.
+    ///
+    /// `Full` checks structure and the vertex signature and requires a
+    /// ZK-STARK proof to be attached; `Header` trusts the payload but still
+    /// checks the signature and the proof's presence so the node can follow
+    /// the chain tip cheaply; `None` skips validation entirely (tip-tracking/relay only).
+    /// No level verifies the proof's contents or checks that parents exist.
+    fn verify_transaction(&self, transaction: &Transaction) -> Result<(), SDUPIError> {
+        if self.config.verification_level == VerificationLevel::None {
+            return Ok(());
+        }
+
+        if self.config.verification_level == VerificationLevel::Full {
+            transaction.validate_structure()?;
+        }
+
+        if let Some(signature) = &transaction.signature {
+            crate::crypto::utils::verify_transaction_signature(
+                &transaction.sender,
+                &transaction.hash(),
+                signature,
+            )?;
+        } else {
+            return Err(SDUPIError::TransactionValidation("Transaction is missing a signature".to_string()));
+        }
+
+        // Parent *existence* is intentionally not checked here: a missing
+        // parent isn't invalid, just not-yet-arrived, and is handled by
+        // `add_transaction_advanced`'s orphan buffer instead of rejection.
+
+        if transaction.zk_proof.is_none() {
+            return Err(SDUPIError::ZKSTARKVerification("Transaction is missing a ZK-STARK proof".to_string()));
+        }
+
+        Ok(())
+    }
+
     /// Add transaction with advanced optimizations
     pub async fn add_transaction_advanced(&self, transaction: Transaction) -> Result<(), SDUPIError> {
         let start_time = Instant::now();
-        
-        // Validate transaction structure
-        transaction.validate_structure()?;
-        
-        // Check predictive cache for conflicts
+
+        // Validate according to the configured verification level
+        self.verify_transaction(&transaction)?;
+
+        // Reject dust before allocating anything for it
+        if transaction.fee < self.config.min_effective_fee {
+            return Err(SDUPIError::TransactionValidation(format!(
+                "Transaction fee {} is below the minimum effective fee {}",
+                transaction.fee, self.config.min_effective_fee
+            )));
+        }
+
+        // Fee-bump replacement: at most one pending transaction per sender.
+        // A newcomer evicts it only once its fee clears the bump factor.
+        if let Some((conflicting_id, existing_fee)) = self.find_conflicting_pending(&transaction.sender)? {
+            let required_fee = (existing_fee as f64 * (1.0 + self.config.fee_bump_factor)).ceil() as u64;
+            if transaction.fee < required_fee {
+                return Err(SDUPIError::TransactionValidation(format!(
+                    "Replacement transaction fee {} does not beat pending transaction's fee {} by the required bump factor",
+                    transaction.fee, existing_fee
+                )));
+            }
+            self.evict_transaction(&conflicting_id)?;
+            let mut metrics = self.performance_metrics.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            metrics.fee_bump_evictions += 1;
+        }
+
+        // Check predictive cache for conflicts, using the senders of the
+        // current tip frontier for the model's `tip_overlap_ratio` feature
         let conflict_probability = {
-            let predictor = self.ai_conflict_predictor.read()
-                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
-            predictor.predict_conflict(&transaction)
+            let tip_senders: Vec<PublicKey> = {
+                let tip_cache = self.tip_cache.read()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+                let transactions = self.transactions.read()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+                tip_cache.iter()
+                    .filter_map(|id| transactions.get(id).map(|node| node.transaction.sender.clone()))
+                    .collect()
+            };
+            let mut predictor = self.ai_conflict_predictor.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            predictor.predict_conflict(&transaction, &tip_senders)
         };
         
         // Create advanced DAG node
+        let weight = self.transaction_weight(&transaction);
         let node = AdvancedDAGNode {
             transaction: transaction.clone(),
             children: HashSet::new(),
-            weight: 0,
+            weight,
             validated_at: None,
-            performance_metrics: NodePerformanceMetrics::default(),
+            performance_metrics: NodePerformanceMetrics {
+                weight,
+                fee_per_weight: transaction.fee as f64 / weight.max(1) as f64,
+                conflict_probability,
+                ..NodePerformanceMetrics::default()
+            },
             cached_hash: None,
-            priority_score: self.calculate_priority_score(&transaction, conflict_probability),
+            priority_score: self.calculate_priority_score(&transaction, conflict_probability, weight),
         };
-        
+
         let transaction_id = node.transaction.id;
-        
-        // Add to transactions storage with zero-copy optimization
+
+        // A referenced parent that hasn't arrived yet isn't an error, it's
+        // just not-yet-arrived: park the node in the orphan buffer instead
+        // of admitting it, and let `promote_orphans` admit it once the last
+        // missing parent shows up.
+        let missing_parents: HashSet<Uuid> = {
+            let transactions = self.transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            [node.transaction.parent1, node.transaction.parent2]
+                .into_iter()
+                .flatten()
+                .filter(|parent_id| !transactions.contains_key(parent_id))
+                .collect()
+        };
+
+        if !missing_parents.is_empty() {
+            self.park_orphan(node, missing_parents)?;
+            return Ok(());
+        }
+
+        self.admit_node(transaction_id, node)?;
+        self.promote_orphans(transaction_id)?;
+
+        // Update predictive cache
+        self.update_predictive_cache(&transaction).await?;
+
+        // Update tip cache with parallel optimization
+        self.update_tip_cache_parallel().await?;
+
+        // Update performance metrics
+        let processing_time = start_time.elapsed();
+        self.update_performance_metrics(processing_time).await?;
+
+        Ok(())
+    }
+
+    /// Insert an `AdvancedDAGNode` directly into storage and the
+    /// priority-ordered pending queue. Used both for transactions admitted
+    /// straight away and for orphans promoted once their parents arrive.
+    fn admit_node(&self, transaction_id: Uuid, node: AdvancedDAGNode) -> Result<(), SDUPIError> {
+        let priority_score = node.priority_score;
+        let weight = node.weight;
+        let fee_per_weight = node.performance_metrics.fee_per_weight;
+        let parents = [node.transaction.parent1, node.transaction.parent2];
+
         {
             let mut transactions = self.transactions.write()
                 .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
             transactions.insert(transaction_id, node);
-        }
-        
-        // Add to priority-ordered pending queue
+            for parent_id in parents.into_iter().flatten() {
+                if let Some(parent_node) = transactions.get_mut(&parent_id) {
+                    parent_node.children.insert(transaction_id);
+                }
+            }
+        }
+
         {
             let mut pending = self.pending_queue.write()
                 .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
-            
-            // Insert at appropriate position based on priority
+            let transactions = self.transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+
             let mut inserted = false;
             for (i, &pending_id) in pending.iter().enumerate() {
-                if let Some(pending_node) = self.transactions.read().ok()?.get(&pending_id) {
-                    if node.priority_score > pending_node.priority_score {
+                if let Some(pending_node) = transactions.get(&pending_id) {
+                    if priority_score > pending_node.priority_score {
                         pending.insert(i, transaction_id);
                         inserted = true;
                         break;
                     }
                 }
             }
-            
+
             if !inserted {
                 pending.push_back(transaction_id);
             }
         }
-        
-        // Update predictive cache
-        self.update_predictive_cache(&transaction).await?;
-        
-        // Update tip cache with parallel optimization
-        self.update_tip_cache_parallel().await?;
-        
-        // Update performance metrics
-        let processing_time = start_time.elapsed();
-        self.update_performance_metrics(processing_time).await?;
-        
+
+        {
+            let mut metrics = self.performance_metrics.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            metrics.total_weight_processed += weight;
+            metrics.fee_per_weight = (metrics.fee_per_weight + fee_per_weight) / 2.0;
+        }
+
+        self.enforce_pool_capacity()?;
+
         Ok(())
     }
-    
+
+    /// Trim `pending_queue` back down to `config.memory_pool_size`, evicting
+    /// the lowest fee-rate (`fee_per_weight`) entries first, whatever their
+    /// place in the `priority_score`-ordered queue.
+    fn enforce_pool_capacity(&self) -> Result<(), SDUPIError> {
+        let overflow: Vec<Uuid> = {
+            let pending = self.pending_queue.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            if pending.len() <= self.config.memory_pool_size {
+                return Ok(());
+            }
+            let transactions = self.transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            let mut by_fee_rate: Vec<(Uuid, f64)> = pending.iter()
+                .map(|id| (*id, transactions.get(id).map(|node| node.performance_metrics.fee_per_weight).unwrap_or(0.0)))
+                .collect();
+            by_fee_rate.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+            by_fee_rate.into_iter().take(pending.len() - self.config.memory_pool_size).map(|(id, _)| id).collect()
+        };
+
+        for id in &overflow {
+            // A rooted transaction is final and refuses eviction; leave it
+            // in place rather than erroring out the whole admission.
+            if self.evict_transaction(id).is_ok() {
+                let mut metrics = self.performance_metrics.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                metrics.pool_capacity_evictions += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Park a node whose parents haven't all arrived yet, indexing it by
+    /// each missing parent so `promote_orphans` can find it later.
+    fn park_orphan(&self, node: AdvancedDAGNode, missing_parents: HashSet<Uuid>) -> Result<(), SDUPIError> {
+        let transaction_id = node.transaction.id;
+
+        {
+            let mut waiters = self.orphan_waiters.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            for parent_id in &missing_parents {
+                waiters.entry(*parent_id).or_insert_with(Vec::new).push(transaction_id);
+            }
+        }
+
+        let mut orphans = self.orphan_nodes.write()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+        orphans.insert(transaction_id, OrphanEntry {
+            node,
+            missing_parents,
+            parked_at: Instant::now(),
+        });
+
+        Ok(())
+    }
+
+    /// Pull a transaction back out of the pending queue and into the orphan
+    /// buffer after a worker found one of its parents missing (evicted by
+    /// a fee-bump replacement, pool-capacity pressure, or an orphan timeout
+    /// after this transaction was already admitted). It is released the
+    /// same way any orphan is: the next time its parent is (re-)admitted,
+    /// `promote_orphans` picks it back up.
+    fn requeue_missing_parents(&self, ids: &[Uuid]) -> Result<(), SDUPIError> {
+        for id in ids {
+            let node = {
+                let mut transactions = self.transactions.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                transactions.remove(id)
+            };
+
+            let node = match node {
+                Some(node) => node,
+                None => continue,
+            };
+
+            {
+                let mut pending = self.pending_queue.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                pending.retain(|pending_id| pending_id != id);
+            }
+
+            let missing_parents: HashSet<Uuid> = {
+                let transactions = self.transactions.read()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+                [node.transaction.parent1, node.transaction.parent2]
+                    .into_iter()
+                    .flatten()
+                    .filter(|parent_id| !transactions.contains_key(parent_id))
+                    .collect()
+            };
+
+            if missing_parents.is_empty() {
+                // The parent reappeared between the worker's check and now;
+                // just admit it straight back in.
+                self.admit_node(*id, node)?;
+            } else {
+                self.park_orphan(node, missing_parents)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// List which of `parent_ids` are not yet present in the DAG, so a
+    /// networking layer can fetch exactly the missing ancestors for an
+    /// orphaned node.
+    pub fn request_missing(&self, parent_ids: &[Uuid]) -> Result<Vec<Uuid>, SDUPIError> {
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        Ok(parent_ids.iter().filter(|id| !transactions.contains_key(id)).cloned().collect())
+    }
+
+    /// Admit any orphans that were waiting on `arrived_id`, recursively
+    /// promoting further descendants whose own waits have now cleared.
+    fn promote_orphans(&self, arrived_id: Uuid) -> Result<(), SDUPIError> {
+        let waiting: Vec<Uuid> = {
+            let mut waiters = self.orphan_waiters.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            waiters.remove(&arrived_id).unwrap_or_default()
+        };
+
+        for orphan_id in waiting {
+            let ready = {
+                let mut orphans = self.orphan_nodes.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                match orphans.get_mut(&orphan_id) {
+                    Some(entry) => {
+                        entry.missing_parents.remove(&arrived_id);
+                        entry.missing_parents.is_empty()
+                    }
+                    None => false,
+                }
+            };
+
+            if ready {
+                let mut orphans = self.orphan_nodes.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                if let Some(entry) = orphans.remove(&orphan_id) {
+                    drop(orphans);
+                    self.admit_node(orphan_id, entry.node)?;
+                    self.promote_orphans(orphan_id)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drop orphans that have outlived `config.orphan_timeout` without their
+    /// parents arriving, mirroring a pacemaker's leader-timeout reset:
+    /// rather than waiting forever, give up and let the sender retry.
+    pub fn evict_timed_out_orphans(&self) -> Result<usize, SDUPIError> {
+        let timed_out: Vec<Uuid> = {
+            let orphans = self.orphan_nodes.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            orphans.iter()
+                .filter(|(_, entry)| entry.parked_at.elapsed() >= self.config.orphan_timeout)
+                .map(|(id, _)| *id)
+                .collect()
+        };
+
+        for orphan_id in &timed_out {
+            let mut orphans = self.orphan_nodes.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            if let Some(entry) = orphans.remove(orphan_id) {
+                drop(orphans);
+                let mut waiters = self.orphan_waiters.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                for parent_id in &entry.missing_parents {
+                    if let Some(waiting) = waiters.get_mut(parent_id) {
+                        waiting.retain(|id| id != orphan_id);
+                    }
+                }
+            }
+        }
+
+        Ok(timed_out.len())
+    }
+
+    /// Halve every latency-histogram bucket, so p50/p95/p99 in
+    /// `get_advanced_statistics` track recent throughput rather than the
+    /// node's entire uptime. Intended to be called periodically (e.g.
+    /// alongside `evict_timed_out_orphans`) by the node's maintenance loop.
+    pub fn decay_latency_histogram(&self) {
+        self.latency_histogram.decay();
+    }
+
+    /// Find a pending transaction from `sender`, if any, along with its fee.
+    /// This ledger has no per-sender sequence number, so "conflicts with a
+    /// pending transaction from the same sender" is approximated as "sender
+    /// already has a transaction pending" -- at most one in flight at a time.
+    fn find_conflicting_pending(&self, sender: &PublicKey) -> Result<Option<(Uuid, u64)>, SDUPIError> {
+        let pending = self.pending_queue.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+
+        for &id in pending.iter() {
+            if let Some(node) = transactions.get(&id) {
+                if &node.transaction.sender == sender {
+                    return Ok(Some((id, node.transaction.fee)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Evict a transaction from `transactions`, `pending_queue` and
+    /// `tip_cache`, e.g. when it is displaced by a higher-fee replacement.
+    /// Rooted transactions are final and refuse eviction.
+    fn evict_transaction(&self, id: &Uuid) -> Result<(), SDUPIError> {
+        {
+            let rooted = self.rooted_transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            if rooted.contains(id) {
+                return Err(SDUPIError::TransactionValidation(
+                    "Cannot evict a rooted transaction".to_string(),
+                ));
+            }
+        }
+        {
+            let mut transactions = self.transactions.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            transactions.remove(id);
+        }
+        {
+            let mut pending = self.pending_queue.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            pending.retain(|pending_id| pending_id != id);
+        }
+        {
+            let mut tip_cache = self.tip_cache.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            tip_cache.retain(|tip_id| tip_id != id);
+        }
+        {
+            let mut propagated = self.propagated.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            propagated.remove(id);
+        }
+        Ok(())
+    }
+
+    /// Pull up to `max` (capped by `config.max_transactions_to_propagate`)
+    /// not-yet-propagated transactions off the front of the priority-ordered
+    /// pending queue, for a networking layer to gossip to peers. Every
+    /// transaction still in `pending_queue` is by construction
+    /// admissible -- the orphan buffer holds anything with unknown parents,
+    /// and dust is rejected before it ever reaches the queue.
+    pub fn ready_transactions(&self, max: usize) -> Result<Vec<Transaction>, SDUPIError> {
+        let cap = max.min(self.config.max_transactions_to_propagate);
+
+        let pending = self.pending_queue.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let propagated = self.propagated.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+
+        Ok(pending.iter()
+            .filter(|id| !propagated.contains(id))
+            .filter_map(|id| transactions.get(id).map(|node| node.transaction.clone()))
+            .take(cap)
+            .collect())
+    }
+
+    /// Record `ids` as already gossiped, so the next `ready_transactions`
+    /// call skips them until their state changes (replacement, confirmation
+    /// or eviction all clear this tracking).
+    pub fn mark_propagated(&self, ids: &[Uuid]) -> Result<(), SDUPIError> {
+        let mut propagated = self.propagated.write()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+        propagated.extend(ids.iter().copied());
+        Ok(())
+    }
+
+    /// Stricter relay set than `ready_transactions`: the same bounded,
+    /// priority-ordered propagation cap, but additionally restricted to
+    /// transactions whose referenced parents have already cleared
+    /// validation. Useful for peers that only want to extend a trusted
+    /// frontier rather than gossip everything sitting in the raw pending
+    /// queue.
+    pub fn get_ready_transactions(&self, max: usize) -> Result<Vec<Transaction>, SDUPIError> {
+        let cap = max.min(self.config.max_transactions_to_propagate);
+
+        let pending = self.pending_queue.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let validated = self.validated_transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let confirmed = self.confirmed_transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let rooted = self.rooted_transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+
+        let is_settled = |id: &Uuid| validated.contains(id) || confirmed.contains(id) || rooted.contains(id);
+
+        Ok(pending.iter()
+            .filter_map(|id| transactions.get(id).map(|node| &node.transaction))
+            .filter(|transaction| {
+                [transaction.parent1, transaction.parent2]
+                    .into_iter()
+                    .flatten()
+                    .all(|parent_id| is_settled(&parent_id))
+            })
+            .take(cap)
+            .cloned()
+            .collect())
+    }
+
     /// Calculate transaction priority score
-    fn calculate_priority_score(&self, transaction: &Transaction, conflict_probability: f64) -> f64 {
+    fn calculate_priority_score(&self, transaction: &Transaction, conflict_probability: f64, weight: u64) -> f64 {
         let mut score = 0.0;
-        
-        // Higher fees = higher priority
-        score += transaction.fee as f64 * 10.0;
-        
+
+        // Fee per unit of metered weight, not raw fee, so a cheap
+        // transaction doesn't lose out to an expensive one that merely
+        // pays a bigger absolute fee. Amplified by a congestion multiplier
+        // so fee-per-weight dominates more as the queue backs up.
+        let fee_per_weight = transaction.fee as f64 / weight.max(1) as f64;
+        let congestion = self.aggregate_queue_weight().unwrap_or(0) as f64 / self.config.max_batch_weight.max(1) as f64;
+        score += fee_per_weight * 10.0 * (1.0 + congestion);
+
         // Lower conflict probability = higher priority
         score += (1.0 - conflict_probability) * 100.0;
-        
+
         // Newer transactions = higher priority
         let age = chrono::Utc::now().signed_duration_since(transaction.timestamp);
         score += 1.0 / (age.num_seconds() as f64 + 1.0) * 50.0;
-        
+
         // Higher amounts = higher priority
         score += (transaction.amount as f64).log10() * 20.0;
-        
+
         score
     }
-    
+
+    /// Metered cost of admitting and validating `transaction`: a flat base
+    /// plus marginal costs for its signature, payload size and referenced
+    /// parents, inspired by base-extrinsic weighting.
+    fn transaction_weight(&self, transaction: &Transaction) -> u64 {
+        let signature_count = transaction.signature.is_some() as u64;
+        let payload_bytes = transaction.signature.as_ref().map(Vec::len).unwrap_or(0)
+            + transaction.zk_proof.as_ref().map(Vec::len).unwrap_or(0);
+        let parent_count = [transaction.parent1, transaction.parent2].into_iter().flatten().count() as u64;
+
+        BASE_TRANSACTION_WEIGHT
+            + signature_count * PER_SIGNATURE_WEIGHT
+            + payload_bytes as u64 * PER_PAYLOAD_BYTE_WEIGHT
+            + parent_count * PER_PARENT_WEIGHT
+    }
+
+    /// Total metered weight of every transaction currently in
+    /// `pending_queue`, the congestion signal fed into
+    /// `calculate_priority_score`.
+    fn aggregate_queue_weight(&self) -> Result<u64, SDUPIError> {
+        let pending = self.pending_queue.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        Ok(pending.iter().filter_map(|id| transactions.get(id).map(|node| node.weight)).sum())
+    }
+
     /// Update predictive cache
     async fn update_predictive_cache(&self, transaction: &Transaction) -> Result<(), SDUPIError> {
         let pattern = self.extract_transaction_pattern(transaction);
@@ -752,7 +1780,8 @@ impl AdvancedDAGLedger {
         metrics.avg_processing_time = Duration::from_millis(
             (metrics.avg_processing_time.as_millis() + processing_time.as_millis()) / 2
         );
-        
+        self.latency_histogram.record(processing_time);
+
         // Calculate current TPS
         let current_tps = 1.0 / processing_time.as_secs_f64();
         metrics.current_tps = current_tps;
@@ -777,6 +1806,13 @@ impl AdvancedDAGLedger {
         Ok(())
     }
     
+    /// Configured verification level, so callers outside this module (e.g.
+    /// consensus validation workers) can skip expensive validation stages
+    /// for lightweight edge/mobile nodes.
+    pub fn verification_level(&self) -> VerificationLevel {
+        self.config.verification_level
+    }
+
     /// Get performance metrics
     pub fn get_performance_metrics(&self) -> Result<DAGPerformanceMetrics, SDUPIError> {
         let metrics = self.performance_metrics.read()
@@ -784,60 +1820,124 @@ impl AdvancedDAGLedger {
         Ok(metrics.clone())
     }
     
+    /// Assemble a `TransactionBatch` from a weight-packed run of transactions.
+    fn build_batch(transactions: Vec<Transaction>, total_weight: u64, max_batch_weight: u64) -> TransactionBatch {
+        TransactionBatch {
+            batch_id: Uuid::new_v4(),
+            priority_score: transactions.iter().map(|t| t.fee as f64).sum::<f64>(),
+            size: transactions.len(),
+            transactions,
+            timestamp: Instant::now(),
+            total_weight,
+            max_batch_weight,
+        }
+    }
+
     /// Process transactions in parallel batches
     pub async fn process_transactions_parallel(&self) -> Result<usize, SDUPIError> {
         let start_time = Instant::now();
-        
-        // Get pending transactions
-        let pending_transactions = {
+
+        // Get pending transactions paired with their metered weight
+        let pending_transactions: Vec<(Transaction, u64)> = {
             let pending = self.pending_queue.read()
                 .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
-            pending.iter().cloned().collect::<Vec<_>>()
+            let transactions = self.transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            pending.iter()
+                .filter_map(|id| transactions.get(id).map(|node| (node.transaction.clone(), node.weight)))
+                .collect()
         };
-        
-        // Create batches for parallel processing
-        let batches: Vec<TransactionBatch> = pending_transactions
-            .chunks(self.config.batch_size)
-            .map(|chunk| {
-                let transactions: Vec<Transaction> = chunk.iter()
-                    .filter_map(|&id| self.transactions.read().ok()?.get(&id))
-                    .map(|node| node.transaction.clone())
-                    .collect();
-                
-                TransactionBatch {
-                    batch_id: Uuid::new_v4(),
-                    transactions,
-                    timestamp: Instant::now(),
-                    size: transactions.len(),
-                    priority_score: transactions.iter().map(|t| t.fee as f64).sum::<f64>(),
-                }
-            })
-            .collect();
-        
+
+        // Pack batches by metered weight rather than raw transaction count,
+        // so a batch of a few expensive transactions costs the processing
+        // worker the same as a batch of many cheap ones. Every batch takes
+        // at least one transaction, even if it alone exceeds the cap, so a
+        // single oversized transaction can't starve the queue.
+        let max_batch_weight = self.config.max_batch_weight;
+        let mut batches: Vec<TransactionBatch> = Vec::new();
+        let mut current: Vec<Transaction> = Vec::new();
+        let mut current_weight = 0u64;
+
+        for (transaction, weight) in pending_transactions {
+            if !current.is_empty() && current_weight + weight > max_batch_weight {
+                batches.push(Self::build_batch(std::mem::take(&mut current), current_weight, max_batch_weight));
+                current_weight = 0;
+            }
+            current_weight += weight;
+            current.push(transaction);
+        }
+        if !current.is_empty() {
+            batches.push(Self::build_batch(current, current_weight, max_batch_weight));
+        }
+
         // Process batches in parallel
         let results: Vec<ProcessingResult> = futures::future::join_all(
             batches.into_iter().enumerate().map(|(worker_id, batch)| {
                 let worker = &self.processing_workers[worker_id % self.processing_workers.len()];
+
+                // Snapshot admission-time priority scores and conflict
+                // probabilities now, while the batch's transactions are
+                // still at hand, for the trace entry below.
+                let trace_snapshot = if self.config.enable_tracing {
+                    let transactions = self.transactions.read().ok();
+                    transactions.map(|transactions| {
+                        batch.transactions.iter()
+                            .map(|t| {
+                                transactions.get(&t.id)
+                                    .map(|node| (node.priority_score, node.performance_metrics.conflict_probability))
+                                    .unwrap_or((0.0, 0.0))
+                            })
+                            .collect::<Vec<(f64, f64)>>()
+                    })
+                } else {
+                    None
+                };
+                let trace_sender = self.trace_sender.clone();
+                let batch_id = batch.batch_id;
+                let transaction_ids: Vec<Uuid> = batch.transactions.iter().map(|t| t.id).collect();
+                let transactions = batch.transactions.clone();
+
                 async move {
-                    let _ = worker.tx_channel.send(batch).await;
-                    // In real implementation, wait for result
-                    ProcessingResult {
-                        batch_id: Uuid::new_v4(),
-                        status: ProcessingStatus::Success,
-                        processed_transactions: vec![],
+                    let (reply_tx, reply_rx) = oneshot::channel();
+                    let _ = worker.tx_channel.send((batch, reply_tx)).await;
+
+                    let result = reply_rx.await.unwrap_or_else(|_| ProcessingResult {
+                        batch_id,
+                        status: ProcessingStatus::Failed,
+                        processed_transactions: Vec::new(),
                         processing_time: Duration::from_millis(0),
                         worker_id,
+                    });
+
+                    if let ProcessingStatus::MissingParents(ref dependent_ids) = result.status {
+                        let _ = self.requeue_missing_parents(dependent_ids);
+                    }
+
+                    if let (Some(sender), Some(snapshot)) = (&trace_sender, trace_snapshot) {
+                        let (priority_scores, conflict_probabilities) = snapshot.into_iter().unzip();
+                        let _ = sender.send(BatchTraceEntry {
+                            batch_id,
+                            transaction_ids,
+                            transactions,
+                            priority_scores,
+                            conflict_probabilities,
+                            worker_id,
+                            status: result.status.clone(),
+                            processing_time_ms: result.processing_time.as_millis(),
+                        });
                     }
+
+                    result
                 }
             })
         ).await;
-        
+
         let total_processed = results.iter().map(|r| r.processed_transactions.len()).sum();
         let processing_time = start_time.elapsed();
-        
+
         // Update performance metrics
         self.update_performance_metrics(processing_time).await?;
-        
+
         Ok(total_processed)
     }
     
@@ -860,7 +1960,13 @@ impl AdvancedDAGLedger {
         
         let performance_metrics = self.performance_metrics.read()
             .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
-        
+
+        let snapshot_root = {
+            let digest = self.prune_digest.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            hex::encode(digest.root)
+        };
+
         Ok(AdvancedDAGStatistics {
             total_transactions: transactions.len(),
             pending_transactions: pending.len(),
@@ -873,31 +1979,443 @@ impl AdvancedDAGLedger {
             memory_utilization: performance_metrics.memory_utilization,
             cache_hit_rate: performance_metrics.cache_hit_rate,
             parallel_efficiency: performance_metrics.parallel_efficiency,
+            fee_bump_evictions: performance_metrics.fee_bump_evictions,
+            pool_capacity_evictions: performance_metrics.pool_capacity_evictions,
+            p50_processing_time: self.latency_histogram.percentile(0.50),
+            p95_processing_time: self.latency_histogram.percentile(0.95),
+            p99_processing_time: self.latency_histogram.percentile(0.99),
+            max_processing_time: self.latency_histogram.max(),
+            pruned_transactions: performance_metrics.pruned_transactions,
+            snapshot_root,
         })
     }
-}
 
-/// Advanced DAG statistics
-#[derive(Debug, Clone)]
-pub struct AdvancedDAGStatistics {
-    pub total_transactions: usize,
-    pub pending_transactions: usize,
-    pub validated_transactions: usize,
-    pub confirmed_transactions: usize,
-    pub tips_count: usize,
-    pub current_tps: f64,
-    pub peak_tps: f64,
-    pub avg_processing_time: Duration,
-    pub memory_utilization: f64,
-    pub cache_hit_rate: f64,
-    pub parallel_efficiency: f64,
-}
+    /// Confirm a transaction that consensus has finalized: marks it
+    /// confirmed on the node itself and moves its id out of the pending
+    /// queue and into `confirmed_transactions`, so callers polling
+    /// `get_advanced_statistics`/`get_tips` see its real status.
+    pub fn confirm_transaction(&self, id: &Uuid) -> Result<(), SDUPIError> {
+        {
+            let mut transactions = self.transactions.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            if let Some(node) = transactions.get_mut(id) {
+                node.transaction.mark_confirmed();
+                node.validated_at = Some(Instant::now());
+            }
+        }
+        {
+            let mut pending = self.pending_queue.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            pending.retain(|pending_id| pending_id != id);
+        }
+        {
+            let mut confirmed = self.confirmed_transactions.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            confirmed.insert(*id);
+        }
+        {
+            let mut propagated = self.propagated.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            propagated.remove(id);
+        }
 
-// Legacy compatibility
-impl AdvancedDAGLedger {
-    /// Legacy add_transaction method for compatibility
-    pub fn add_transaction(&self, transaction: Transaction) -> Result<(), SDUPIError> {
-        // Use tokio runtime for async call
+        // A transaction that reached confirmation cleanly is a label-0
+        // (no-conflict) outcome for the online conflict predictor.
+        self.record_conflict_outcome(id, 0.0)?;
+
+        // See if enough descendant weight has now built up on top of this
+        // transaction to root it (and its ancestors) into a checkpoint.
+        self.try_root_transaction(id)?;
+
+        Ok(())
+    }
+
+    /// Looks up a transaction by id without mutating anything.
+    pub fn get_transaction(&self, id: &Uuid) -> Option<Transaction> {
+        self.transactions.read().ok()?.get(id).map(|node| node.transaction.clone())
+    }
+
+    /// Marks a transaction rejected and persists that through the ledger:
+    /// updates the stored node's status, drops it from the pending queue
+    /// and propagation set, and records a conflict outcome for the online
+    /// predictor. Unlike mutating a [`get_transaction`](Self::get_transaction)
+    /// copy, this is the only way a rejection actually sticks.
+    pub fn reject_transaction(&self, id: &Uuid) -> Result<(), SDUPIError> {
+        {
+            let mut transactions = self.transactions.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            if let Some(node) = transactions.get_mut(id) {
+                node.transaction.mark_rejected();
+            }
+        }
+        {
+            let mut pending = self.pending_queue.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            pending.retain(|pending_id| pending_id != id);
+        }
+        {
+            let mut propagated = self.propagated.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            propagated.remove(id);
+        }
+
+        // A rejected transaction is a label-1 (conflict) outcome for the
+        // online conflict predictor.
+        self.record_conflict_outcome(id, 1.0)?;
+
+        Ok(())
+    }
+
+    /// Sum the weight of every descendant reachable from `id` through the
+    /// `children` links, `id`'s own weight included.
+    fn cumulative_descendant_weight(&self, id: &Uuid) -> Result<u64, SDUPIError> {
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+
+        let mut total = 0u64;
+        let mut visited = HashSet::new();
+        let mut stack = vec![*id];
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(node) = transactions.get(&current) {
+                total += node.weight;
+                stack.extend(node.children.iter().copied());
+            }
+        }
+        Ok(total)
+    }
+
+    /// If `id`'s cumulative descendant weight has crossed
+    /// `config.rooting_weight_threshold`, root it and every one of its
+    /// unrooted ancestors, and fold the newly-rooted ids into a fresh
+    /// checkpoint chained off the previous one.
+    fn try_root_transaction(&self, id: &Uuid) -> Result<(), SDUPIError> {
+        if self.cumulative_descendant_weight(id)? < self.config.rooting_weight_threshold {
+            return Ok(());
+        }
+
+        let mut newly_rooted = Vec::new();
+        {
+            let transactions = self.transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            let mut rooted = self.rooted_transactions.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+
+            let mut stack = vec![*id];
+            let mut visited = HashSet::new();
+            while let Some(current) = stack.pop() {
+                if !visited.insert(current) {
+                    continue;
+                }
+                if rooted.contains(&current) {
+                    continue;
+                }
+                rooted.insert(current);
+                newly_rooted.push(current);
+                if let Some(node) = transactions.get(&current) {
+                    stack.extend([node.transaction.parent1, node.transaction.parent2].into_iter().flatten());
+                }
+            }
+        }
+
+        if newly_rooted.is_empty() {
+            return Ok(());
+        }
+
+        {
+            let mut prune_queue = self.prune_queue.write()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+            prune_queue.extend(newly_rooted.iter().copied());
+        }
+
+        let mut checkpoints = self.checkpoints.write()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+        let parent = checkpoints.last().map(|checkpoint| checkpoint.id);
+        let sequence = checkpoints.len() as u64;
+        checkpoints.push(Checkpoint {
+            id: Uuid::new_v4(),
+            sequence,
+            rooted_frontier: newly_rooted,
+            parent,
+            created_at: Instant::now(),
+        });
+
+        Ok(())
+    }
+
+    /// Current lifecycle state of `id`: `Rooted`/`Confirmed`/`Validated`
+    /// take priority over `Pending`, and ids this node has never seen
+    /// resolve to `Unknown`.
+    pub fn get_transaction_status(&self, id: &Uuid) -> Result<TransactionState, SDUPIError> {
+        let rooted = self.rooted_transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        if rooted.contains(id) {
+            return Ok(TransactionState::Rooted);
+        }
+
+        let confirmed = self.confirmed_transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        if confirmed.contains(id) {
+            return Ok(TransactionState::Confirmed);
+        }
+
+        let validated = self.validated_transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        if validated.contains(id) {
+            return Ok(TransactionState::Validated);
+        }
+
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        if transactions.contains_key(id) {
+            return Ok(TransactionState::Pending);
+        }
+
+        Ok(TransactionState::Unknown)
+    }
+
+    /// Most recent checkpoint, for a light client to sync forward from
+    /// instead of replaying the whole DAG.
+    pub fn latest_checkpoint(&self) -> Result<Option<Checkpoint>, SDUPIError> {
+        let checkpoints = self.checkpoints.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        Ok(checkpoints.last().cloned())
+    }
+
+    /// Snapshot of the bounded in-memory batch trace, oldest first.
+    pub fn trace_log(&self) -> Result<Vec<BatchTraceEntry>, SDUPIError> {
+        let log = self.trace_log.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        Ok(log.iter().cloned().collect())
+    }
+
+    /// Re-feed a newline-delimited-JSON batch trace (as written by a node
+    /// with `enable_tracing` set) through the worker pipeline against a
+    /// fresh ledger, and confirm it reproduces the same `ProcessingStatus`
+    /// sequence -- making a throughput experiment or a conflict-resolution
+    /// bug report fully reproducible.
+    pub async fn replay_trace(path: &std::path::Path) -> Result<(), SDUPIError> {
+        let contents = std::fs::read_to_string(path)?;
+        let original: Vec<BatchTraceEntry> = contents.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut config = AdvancedDAGConfig::default();
+        config.enable_tracing = true;
+        let ledger = AdvancedDAGLedger::new(config);
+
+        for entry in &original {
+            for transaction in &entry.transactions {
+                ledger.add_transaction_advanced(transaction.clone()).await?;
+            }
+            ledger.process_transactions_parallel().await?;
+            // Give the background trace writer a moment to drain this
+            // batch's entry before moving on to the next one.
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let replayed = ledger.trace_log()?;
+        if replayed.len() != original.len() {
+            return Err(SDUPIError::Consensus(format!(
+                "Replay produced {} batches, trace recorded {}",
+                replayed.len(),
+                original.len()
+            )));
+        }
+
+        for (expected, actual) in original.iter().zip(replayed.iter()) {
+            if expected.status != actual.status {
+                return Err(SDUPIError::Consensus(format!(
+                    "Replay diverged on batch {}: expected {:?}, got {:?}",
+                    expected.batch_id, expected.status, actual.status
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Feed the true outcome for `id` back into the AI conflict predictor:
+    /// `label` 0.0 for confirmed-without-conflict, 1.0 for conflict-resolved.
+    /// Called by `confirm_transaction` and by conflict resolution once it
+    /// determines a transaction had to be resolved against a competitor.
+    pub fn record_conflict_outcome(&self, id: &Uuid, label: f64) -> Result<(), SDUPIError> {
+        let transaction = {
+            let transactions = self.transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            match transactions.get(id) {
+                Some(node) => node.transaction.clone(),
+                None => return Ok(()),
+            }
+        };
+        let tip_senders: Vec<PublicKey> = {
+            let tip_cache = self.tip_cache.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            let transactions = self.transactions.read()
+                .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+            tip_cache.iter()
+                .filter_map(|tip_id| transactions.get(tip_id).map(|node| node.transaction.sender.clone()))
+                .collect()
+        };
+
+        let mut predictor = self.ai_conflict_predictor.write()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+        predictor.observe_outcome(&transaction, &tip_senders, label);
+        Ok(())
+    }
+
+    /// Work off up to `config.prune_batch_size` rooted transactions from the
+    /// front of `prune_queue`: anything old enough (`prune_after`) or buried
+    /// deep enough (`prune_confirmation_depth`) and with no still-unsettled
+    /// child depending on its presence in `transactions` is folded into the
+    /// rolling digest and evicted from the hot in-memory maps. Returns how
+    /// many nodes were evicted. Safe to call periodically (e.g. alongside
+    /// `evict_timed_out_orphans`); the bounded batch size keeps a single
+    /// call's cost fixed regardless of how large the backlog has grown.
+    pub fn prune_confirmed(&self) -> Result<usize, SDUPIError> {
+        let mut evicted = 0usize;
+
+        for _ in 0..self.config.prune_batch_size {
+            let id = {
+                let mut queue = self.prune_queue.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                match queue.pop_front() {
+                    Some(id) => id,
+                    None => break,
+                }
+            };
+
+            let node = {
+                let transactions = self.transactions.read()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+                transactions.get(&id).cloned()
+            };
+            let node = match node {
+                Some(node) => node,
+                // Already evicted (e.g. pruned on an earlier pass) or never
+                // admitted in the first place.
+                None => continue,
+            };
+
+            let old_enough = node.validated_at
+                .map(|confirmed_at| confirmed_at.elapsed() >= self.config.prune_after)
+                .unwrap_or(false);
+            let deep_enough = self.cumulative_descendant_weight(&id)? >= self.config.prune_confirmation_depth;
+
+            if !(old_enough || deep_enough) {
+                // `prune_queue` is ordered oldest-rooted-first, so nothing
+                // behind this entry is readier either; stop this pass here
+                // and let a later call pick back up where it left off.
+                let mut queue = self.prune_queue.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                queue.push_front(id);
+                break;
+            }
+
+            // A child that hasn't reached Confirmed/Rooted yet will still
+            // have its parent-existence checked by `add_transaction_advanced`
+            // or `process_transactions_parallel`'s worker loop, so this node
+            // must stay in `transactions` until every such child settles.
+            let still_needed = {
+                let transactions = self.transactions.read()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+                let confirmed = self.confirmed_transactions.read()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+                let rooted = self.rooted_transactions.read()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+                node.children.iter().any(|child_id| {
+                    transactions.contains_key(child_id)
+                        && !confirmed.contains(child_id)
+                        && !rooted.contains(child_id)
+                })
+            };
+
+            if still_needed {
+                // Not safe yet -- retry once its children have settled.
+                let mut queue = self.prune_queue.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                queue.push_back(id);
+                continue;
+            }
+
+            let payload_hash = node.transaction.hash();
+            {
+                let mut transactions = self.transactions.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                transactions.remove(&id);
+            }
+            {
+                let mut rooted = self.rooted_transactions.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                rooted.remove(&id);
+            }
+            {
+                let mut confirmed = self.confirmed_transactions.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                confirmed.remove(&id);
+            }
+            {
+                let mut digest = self.prune_digest.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                digest.fold(&id, &payload_hash);
+            }
+            {
+                let mut metrics = self.performance_metrics.write()
+                    .map_err(|_| SDUPIError::Storage("Failed to acquire write lock".to_string()))?;
+                metrics.pruned_transactions += 1;
+            }
+
+            evicted += 1;
+        }
+
+        Ok(evicted)
+    }
+
+    /// Compact, on-demand summary of everything pruned so far: the rolling
+    /// digest root plus how many nodes have been folded into it, so a peer
+    /// can verify a claimed pruned history without the original nodes.
+    pub fn create_snapshot(&self) -> Result<PruneSnapshot, SDUPIError> {
+        let digest = self.prune_digest.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+        Ok(PruneSnapshot {
+            root: hex::encode(digest.root),
+            pruned_count: digest.count,
+        })
+    }
+}
+
+/// Advanced DAG statistics
+#[derive(Debug, Clone)]
+pub struct AdvancedDAGStatistics {
+    pub total_transactions: usize,
+    pub pending_transactions: usize,
+    pub validated_transactions: usize,
+    pub confirmed_transactions: usize,
+    pub tips_count: usize,
+    pub current_tps: f64,
+    pub peak_tps: f64,
+    pub avg_processing_time: Duration,
+    pub memory_utilization: f64,
+    pub cache_hit_rate: f64,
+    pub parallel_efficiency: f64,
+    pub fee_bump_evictions: u64,
+    pub pool_capacity_evictions: u64,
+    pub p50_processing_time: Duration,
+    pub p95_processing_time: Duration,
+    pub p99_processing_time: Duration,
+    pub max_processing_time: Duration,
+    pub pruned_transactions: u64,
+    pub snapshot_root: String,
+}
+
+// Legacy compatibility
+impl AdvancedDAGLedger {
+    /// Legacy add_transaction method for compatibility
+    pub fn add_transaction(&self, transaction: Transaction) -> Result<(), SDUPIError> {
+        // Use tokio runtime for async call
         tokio::runtime::Runtime::new()
             .unwrap()
             .block_on(self.add_transaction_advanced(transaction))
@@ -913,7 +2431,7 @@ impl AdvancedDAGLedger {
     /// Legacy get_statistics method for compatibility
     pub fn get_statistics(&self) -> Result<DAGStatistics, SDUPIError> {
         let advanced_stats = self.get_advanced_statistics()?;
-        
+
         Ok(DAGStatistics {
             total_transactions: advanced_stats.total_transactions,
             pending_transactions: advanced_stats.pending_transactions,
@@ -922,6 +2440,33 @@ impl AdvancedDAGLedger {
             tips_count: advanced_stats.tips_count,
         })
     }
+
+    /// Page through transactions for DAG sync, ordered by `(timestamp, id)`.
+    /// `from` excludes everything up to and including that transaction;
+    /// `None` starts from the beginning. Returns the page plus whether more
+    /// transactions remain beyond it, so a request/response sync protocol
+    /// can page through a node's full history without flooding the network.
+    pub fn transactions_after(&self, from: Option<Uuid>, limit: usize) -> Result<(Vec<Transaction>, bool), SDUPIError> {
+        let transactions = self.transactions.read()
+            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
+
+        let mut ordered: Vec<&AdvancedDAGNode> = transactions.values().collect();
+        ordered.sort_by_key(|node| (node.transaction.timestamp, node.transaction.id));
+
+        let start = match from {
+            Some(from_id) => ordered.iter()
+                .position(|node| node.transaction.id == from_id)
+                .map(|i| i + 1)
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        let remaining = ordered.len().saturating_sub(start);
+        let page: Vec<Transaction> = ordered[start..].iter().take(limit).map(|node| node.transaction.clone()).collect();
+        let has_more = remaining > page.len();
+
+        Ok((page, has_more))
+    }
 }
 
 #[cfg(test)]
@@ -968,11 +2513,11 @@ mod tests {
     
     #[test]
     fn test_ai_conflict_predictor() {
-        let predictor = AIConflictPredictor::new();
-        
+        let mut predictor = AIConflictPredictor::new();
+
         let keypair = KeyPair::generate();
         let recipient = KeyPair::generate().public_key();
-        
+
         let transaction = Transaction::new(
             keypair.public_key(),
             recipient,
@@ -981,19 +2526,375 @@ mod tests {
             None,
             None,
         );
-        
-        let conflict_probability = predictor.predict_conflict(&transaction);
+
+        let conflict_probability = predictor.predict_conflict(&transaction, &[]);
         assert!(conflict_probability >= 0.0 && conflict_probability <= 1.0);
+
+        predictor.observe_outcome(&transaction, &[], 0.0);
+        assert!(predictor.accuracy >= 0.0 && predictor.accuracy <= 1.0);
+        assert_eq!(predictor.training_data.len(), 1);
     }
     
     #[tokio::test]
     async fn test_advanced_dag_ledger_creation() {
         let config = AdvancedDAGConfig::default();
         let ledger = AdvancedDAGLedger::new(config);
-        
+
         let stats = ledger.get_advanced_statistics().unwrap();
         assert_eq!(stats.total_transactions, 0);
         assert_eq!(stats.pending_transactions, 0);
         assert_eq!(stats.tips_count, 0);
     }
+
+    #[tokio::test]
+    async fn test_transaction_status_and_rooting() {
+        let mut config = AdvancedDAGConfig::default();
+        config.rooting_weight_threshold = 1;
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+        let mut transaction = Transaction::new(keypair.public_key(), recipient, 1000, 10, None, None);
+        transaction.signature = Some(vec![1, 2, 3]);
+        transaction.zk_proof = Some(vec![4, 5, 6]);
+        let id = transaction.id;
+
+        assert_eq!(ledger.get_transaction_status(&id).unwrap(), TransactionState::Unknown);
+
+        // Signature/proof are placeholders, so go through `admit_node`
+        // directly rather than the full `verify_transaction` path.
+        let node = AdvancedDAGNode {
+            transaction,
+            children: HashSet::new(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 0.0,
+        };
+        ledger.admit_node(id, node).unwrap();
+        assert_eq!(ledger.get_transaction_status(&id).unwrap(), TransactionState::Pending);
+
+        ledger.confirm_transaction(&id).unwrap();
+        assert_eq!(ledger.get_transaction_status(&id).unwrap(), TransactionState::Rooted);
+
+        let checkpoint = ledger.latest_checkpoint().unwrap().unwrap();
+        assert_eq!(checkpoint.sequence, 0);
+        assert!(checkpoint.rooted_frontier.contains(&id));
+    }
+
+    #[tokio::test]
+    async fn test_ready_transactions_and_mark_propagated() {
+        let config = AdvancedDAGConfig::default();
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+        let transaction = Transaction::new(keypair.public_key(), recipient, 1000, 10, None, None);
+        let id = transaction.id;
+
+        let node = AdvancedDAGNode {
+            transaction,
+            children: HashSet::new(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 0.0,
+        };
+        ledger.admit_node(id, node).unwrap();
+
+        let ready = ledger.ready_transactions(10).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, id);
+
+        ledger.mark_propagated(&[id]).unwrap();
+        assert!(ledger.ready_transactions(10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_weight_and_batch_packing() {
+        let config = AdvancedDAGConfig::default();
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+        let mut transaction = Transaction::new(keypair.public_key(), recipient, 1000, 10, None, None);
+        transaction.signature = Some(vec![0u8; 64]);
+        transaction.zk_proof = Some(vec![0u8; 32]);
+
+        let weight = ledger.transaction_weight(&transaction);
+        assert_eq!(weight, BASE_TRANSACTION_WEIGHT + PER_SIGNATURE_WEIGHT + (64 + 32) * PER_PAYLOAD_BYTE_WEIGHT);
+
+        let batch = AdvancedDAGLedger::build_batch(vec![transaction.clone(), transaction], weight * 2, 1_000_000);
+        assert_eq!(batch.total_weight, weight * 2);
+        assert_eq!(batch.size, 2);
+        assert!(batch.total_weight <= batch.max_batch_weight);
+    }
+
+    #[tokio::test]
+    async fn test_batch_tracing_and_replay() {
+        let dir = std::env::temp_dir();
+        let trace_path = dir.join(format!("sdupi_trace_test_{}.jsonl", Uuid::new_v4()));
+
+        let mut config = AdvancedDAGConfig::default();
+        config.enable_tracing = true;
+        config.trace_log_path = Some(trace_path.clone());
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+        let mut transaction = Transaction::new(keypair.public_key(), recipient, 1000, 10, None, None);
+        transaction.signature = Some(keypair.sign_transaction(&transaction.hash()));
+        transaction.zk_proof = Some(vec![0u8; 32]);
+        ledger.add_transaction_advanced(transaction).await.unwrap();
+        ledger.process_transactions_parallel().await.unwrap();
+
+        // Let the background trace writer drain the channel.
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let log = ledger.trace_log().unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].status, ProcessingStatus::Success);
+
+        AdvancedDAGLedger::replay_trace(&trace_path).await.unwrap();
+
+        let _ = std::fs::remove_file(&trace_path);
+    }
+
+    #[tokio::test]
+    async fn test_pool_capacity_eviction() {
+        let mut config = AdvancedDAGConfig::default();
+        config.memory_pool_size = 2;
+        let ledger = AdvancedDAGLedger::new(config);
+
+        for fee in [10u64, 20, 30] {
+            let keypair = KeyPair::generate();
+            let recipient = KeyPair::generate().public_key();
+            let mut transaction = Transaction::new(keypair.public_key(), recipient, 1000, fee, None, None);
+            transaction.signature = Some(keypair.sign_transaction(&transaction.hash()));
+            transaction.zk_proof = Some(vec![0u8; 32]);
+            ledger.add_transaction_advanced(transaction).await.unwrap();
+        }
+
+        let ready = ledger.ready_transactions(10).unwrap();
+        assert_eq!(ready.len(), 2);
+        // The lowest-fee transaction was the one evicted to stay within
+        // `memory_pool_size`.
+        assert!(ready.iter().all(|t| t.fee > 10));
+
+        let stats = ledger.get_advanced_statistics().unwrap();
+        assert_eq!(stats.pool_capacity_evictions, 1);
+    }
+
+    #[test]
+    fn test_latency_histogram_percentiles() {
+        let histogram = LatencyHistogram::new();
+        for _ in 0..99 {
+            histogram.record(Duration::from_micros(100));
+        }
+        histogram.record(Duration::from_micros(100_000));
+
+        assert!(histogram.percentile(0.50) < Duration::from_micros(1_000));
+        // 99 of the 100 samples sit in the 100us bucket, so p99 is that
+        // bucket's geometric midpoint.
+        assert_eq!(histogram.percentile(0.99), LatencyHistogram::bucket_midpoint(
+            63 - (100u64).leading_zeros() as usize
+        ));
+        assert_eq!(histogram.max(), LatencyHistogram::bucket_midpoint(
+            (63 - (100_000u64).leading_zeros() as usize).min(LATENCY_HISTOGRAM_BUCKETS - 1)
+        ));
+
+        histogram.decay();
+        assert!(histogram.buckets.iter().map(|b| b.load(Ordering::Relaxed)).sum::<u64>() < 100);
+    }
+
+    #[tokio::test]
+    async fn test_get_ready_transactions_requires_settled_parents() {
+        let config = AdvancedDAGConfig::default();
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+
+        let parent = Transaction::new(keypair.public_key(), recipient.clone(), 1000, 10, None, None);
+        let parent_id = parent.id;
+        let mut child = Transaction::new(keypair.public_key(), recipient, 500, 10, None, None);
+        child.parent1 = Some(parent_id);
+        let child_id = child.id;
+
+        ledger.admit_node(parent_id, AdvancedDAGNode {
+            transaction: parent,
+            children: HashSet::new(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 1.0,
+        }).unwrap();
+        ledger.admit_node(child.id, AdvancedDAGNode {
+            transaction: child,
+            children: HashSet::new(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 0.5,
+        }).unwrap();
+
+        // The child's parent hasn't been validated/confirmed/rooted yet, so
+        // only the parentless parent itself is ready.
+        let ready = ledger.get_ready_transactions(10).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, parent_id);
+
+        ledger.confirm_transaction(&parent_id).unwrap();
+
+        let ready = ledger.get_ready_transactions(10).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id, child_id);
+    }
+
+    #[tokio::test]
+    async fn test_process_transactions_requeues_missing_parents() {
+        let config = AdvancedDAGConfig::default();
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+
+        let parent_id = Uuid::new_v4();
+        let mut child = Transaction::new(keypair.public_key(), recipient, 500, 10, None, None);
+        child.parent1 = Some(parent_id);
+        let child_id = child.id;
+
+        // Admit the child directly, bypassing the orphan-at-admission-time
+        // check, to simulate its parent having been evicted after it was
+        // originally admitted.
+        ledger.admit_node(child_id, AdvancedDAGNode {
+            transaction: child,
+            children: HashSet::new(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 1.0,
+        }).unwrap();
+
+        let processed = ledger.process_transactions_parallel().await.unwrap();
+        assert_eq!(processed, 0);
+
+        // The child should have been pulled back out of the pending queue
+        // and parked as an orphan rather than counted as processed.
+        assert!(ledger.ready_transactions(10).unwrap().is_empty());
+
+        // Once the parent (re-)arrives, the child is released back in.
+        let mut parent = Transaction::new(keypair.public_key(), KeyPair::generate().public_key(), 1000, 10, None, None);
+        parent.id = parent_id;
+        parent.signature = Some(keypair.sign_transaction(&parent.hash()));
+        parent.zk_proof = Some(vec![0u8; 32]);
+        ledger.add_transaction_advanced(parent).await.unwrap();
+
+        let ready = ledger.ready_transactions(10).unwrap();
+        assert!(ready.iter().any(|t| t.id == child_id));
+    }
+
+    #[tokio::test]
+    async fn test_prune_confirmed_evicts_settled_root_and_builds_digest() {
+        let mut config = AdvancedDAGConfig::default();
+        config.rooting_weight_threshold = 1;
+        config.prune_after = Duration::from_millis(0);
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+        let mut transaction = Transaction::new(keypair.public_key(), recipient, 1000, 10, None, None);
+        transaction.signature = Some(vec![1, 2, 3]);
+        transaction.zk_proof = Some(vec![4, 5, 6]);
+        let id = transaction.id;
+
+        let node = AdvancedDAGNode {
+            transaction,
+            children: HashSet::new(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 0.0,
+        };
+        ledger.admit_node(id, node).unwrap();
+        ledger.confirm_transaction(&id).unwrap();
+        assert_eq!(ledger.get_transaction_status(&id).unwrap(), TransactionState::Rooted);
+
+        let empty_snapshot = ledger.create_snapshot().unwrap();
+        assert_eq!(empty_snapshot.pruned_count, 0);
+
+        let evicted = ledger.prune_confirmed().unwrap();
+        assert_eq!(evicted, 1);
+
+        // Pruned out of the hot map entirely, but the id's history is still
+        // verifiable through the rolling digest.
+        assert_eq!(ledger.get_transaction_status(&id).unwrap(), TransactionState::Unknown);
+        let snapshot = ledger.create_snapshot().unwrap();
+        assert_eq!(snapshot.pruned_count, 1);
+        assert_ne!(snapshot.root, empty_snapshot.root);
+
+        let stats = ledger.get_advanced_statistics().unwrap();
+        assert_eq!(stats.pruned_transactions, 1);
+        assert_eq!(stats.snapshot_root, snapshot.root);
+
+        // A second pass has nothing left to do.
+        assert_eq!(ledger.prune_confirmed().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_prune_confirmed_keeps_ancestor_needed_by_pending_child() {
+        let mut config = AdvancedDAGConfig::default();
+        config.rooting_weight_threshold = 1;
+        config.prune_after = Duration::from_millis(0);
+        let ledger = AdvancedDAGLedger::new(config);
+
+        let keypair = KeyPair::generate();
+        let recipient = KeyPair::generate().public_key();
+        let mut parent = Transaction::new(keypair.public_key(), recipient.clone(), 1000, 10, None, None);
+        parent.signature = Some(vec![1, 2, 3]);
+        parent.zk_proof = Some(vec![4, 5, 6]);
+        let parent_id = parent.id;
+
+        let mut child = Transaction::new(keypair.public_key(), recipient, 500, 10, None, None);
+        child.parent1 = Some(parent_id);
+        let child_id = child.id;
+
+        ledger.admit_node(parent_id, AdvancedDAGNode {
+            transaction: parent,
+            children: [child_id].into_iter().collect(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 1.0,
+        }).unwrap();
+        ledger.admit_node(child_id, AdvancedDAGNode {
+            transaction: child,
+            children: HashSet::new(),
+            weight: 1,
+            validated_at: None,
+            performance_metrics: NodePerformanceMetrics::default(),
+            cached_hash: None,
+            priority_score: 0.5,
+        }).unwrap();
+
+        ledger.confirm_transaction(&parent_id).unwrap();
+        assert_eq!(ledger.get_transaction_status(&parent_id).unwrap(), TransactionState::Rooted);
+
+        // The child is still pending, so the parent must not be pruned out
+        // from under it.
+        assert_eq!(ledger.prune_confirmed().unwrap(), 0);
+        assert_eq!(ledger.get_transaction_status(&parent_id).unwrap(), TransactionState::Rooted);
+
+        ledger.confirm_transaction(&child_id).unwrap();
+        let evicted = ledger.prune_confirmed().unwrap();
+        assert!(evicted >= 1);
+        assert_eq!(ledger.get_transaction_status(&parent_id).unwrap(), TransactionState::Unknown);
+    }
 }
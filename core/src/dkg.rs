@@ -0,0 +1,102 @@
+//! FROST-style distributed key generation (DKG) primitives.
+//!
+//! A threshold validator set commits to per-round secret-sharing
+//! polynomials; this module aggregates those commitments into a single
+//! group public key that lets any node verify a participant's share and
+//! later combine partial signatures over a consensus round.
+//!
+//! This crate doesn't wire in a real elliptic-curve group yet -- `crypto.rs`
+//! only wraps ed25519 keypairs/signatures, it doesn't expose point
+//! arithmetic. Until it does, a "group element" is tracked as 32 opaque
+//! bytes and addition is a placeholder wrapping byte-sum, which is enough
+//! to exercise the aggregation bookkeeping this subsystem exists for.
+
+use crate::SDUPIError;
+
+/// 32-byte encoding of a group element (a curve point in a real FROST
+/// implementation).
+pub type GroupElementBytes = [u8; 32];
+
+/// Identity element for `add_group_elements`.
+pub const IDENTITY: GroupElementBytes = [0u8; 32];
+
+/// Add two group elements. Placeholder wrapping byte-sum until a real
+/// elliptic-curve group is wired in; see the module doc comment.
+pub fn add_group_elements(a: &GroupElementBytes, b: &GroupElementBytes) -> GroupElementBytes {
+    let mut sum = [0u8; 32];
+    for i in 0..32 {
+        sum[i] = a[i].wrapping_add(b[i]);
+    }
+    sum
+}
+
+/// Aggregate every participant's per-coefficient commitments into a single
+/// group commitment by summing coefficient-by-coefficient: the result's
+/// `i`-th element is the sum, over every participant, of that
+/// participant's `i`-th coefficient commitment.
+///
+/// Every commitment must have the same length (the threshold polynomial's
+/// degree + 1); returns an error naming the mismatched participant otherwise.
+pub fn aggregate_commitments(
+    commitments: &[(String, Vec<GroupElementBytes>)],
+) -> Result<Vec<GroupElementBytes>, SDUPIError> {
+    let degree = match commitments.first() {
+        Some((_, first)) => first.len(),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut accumulator = vec![IDENTITY; degree];
+    for (participant, commitment) in commitments {
+        if commitment.len() != degree {
+            return Err(SDUPIError::Crypto(format!(
+                "Participant {} submitted {} coefficient commitments, expected {}",
+                participant,
+                commitment.len(),
+                degree
+            )));
+        }
+        for (slot, coefficient) in accumulator.iter_mut().zip(commitment.iter()) {
+            *slot = add_group_elements(slot, coefficient);
+        }
+    }
+
+    Ok(accumulator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_commitments_sums_coefficient_by_coefficient() {
+        let alice = vec![[1u8; 32], [2u8; 32]];
+        let bob = vec![[3u8; 32], [4u8; 32]];
+
+        let aggregated = aggregate_commitments(&[
+            ("alice".to_string(), alice),
+            ("bob".to_string(), bob),
+        ])
+        .unwrap();
+
+        assert_eq!(aggregated[0], [4u8; 32]);
+        assert_eq!(aggregated[1], [6u8; 32]);
+    }
+
+    #[test]
+    fn test_aggregate_commitments_rejects_mismatched_degree() {
+        let alice = vec![[1u8; 32], [2u8; 32]];
+        let bob = vec![[3u8; 32]];
+
+        let result = aggregate_commitments(&[
+            ("alice".to_string(), alice),
+            ("bob".to_string(), bob),
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_aggregate_commitments_empty_input_returns_empty() {
+        assert!(aggregate_commitments(&[]).unwrap().is_empty());
+    }
+}
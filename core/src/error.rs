@@ -11,6 +11,9 @@ pub enum SDUPIError {
     
     #[error("Network error: {0}")]
     Network(String),
+
+    #[error("Network mismatch: expected magic {0:?}, got {1:?}")]
+    NetworkMismatch([u8; 4], [u8; 4]),
     
     #[error("Storage error: {0}")]
     Storage(String),
@@ -35,7 +38,13 @@ pub enum SDUPIError {
     
     #[error("ZK-STARK verification failed: {0}")]
     ZKSTARKVerification(String),
-    
+
+    #[error("Transaction timestamp drifts too far into the future: {0}")]
+    ClockDriftExceeded(String),
+
+    #[error("Reference to pruned history: {0}")]
+    PrunedBlock(String),
+
     #[error("WASM execution error: {0}")]
     WASMExecution(String),
     
@@ -87,3 +96,9 @@ impl From<String> for SDUPIError {
         SDUPIError::TransactionValidation(err)
     }
 }
+
+impl From<rusqlite::Error> for SDUPIError {
+    fn from(err: rusqlite::Error) -> Self {
+        SDUPIError::Storage(err.to_string())
+    }
+}
@@ -15,15 +15,26 @@ pub mod storage;
 pub mod error;
 pub mod smart_contract;
 pub mod wallet_integrations;
+pub mod snapshot;
+pub mod merkle;
+pub mod dkg;
+pub mod native_keystore;
 
-pub use dag::DAGLedger;
+pub use dag::{DAGLedger, VerificationLevel, TransactionState, Checkpoint};
 pub use transaction::{Transaction, TransactionStatus};
-pub use consensus::ConsensusEngine;
+pub use consensus::{ConsensusEngine, ConsensusParams, Network, Deployment, DeploymentState, default_deployments};
 pub use network::NodeNetwork;
 pub use crypto::KeyPair;
 pub use error::SDUPIError;
-pub use smart_contract::{SmartContractEngine, SmartContract, ContractExecution, CrossChainBridge};
-pub use wallet_integrations::{WalletIntegrationManager, WalletType, WalletConnection, MetaMaskIntegration, PhantomIntegration};
+pub use snapshot::{LedgerSnapshot, ChunkManifest, SnapshotExport, export_snapshot, import_snapshot, apply_snapshot, DEFAULT_CHUNK_SIZE};
+pub use merkle::{MerkleProof, ProofStep, compute_merkle_root, verify_inclusion_proof};
+pub use dkg::{GroupElementBytes, aggregate_commitments};
+pub use native_keystore::{NativeKeyStore, AccountBackup, DEFAULT_DERIVATION_PATH};
+pub use smart_contract::{SmartContractEngine, SmartContract, ContractExecution, CrossChainBridge, GasReceipt};
+pub use wallet_integrations::{
+    WalletIntegrationManager, WalletType, WalletConnection, MetaMaskIntegration, PhantomIntegration,
+    WalletConnectIntegration, WalletConnectPairing, WalletConnectSettlement,
+};
 
 /// Result type for SDUPI operations
 pub type Result<T> = std::result::Result<T, SDUPIError>;
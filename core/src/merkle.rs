@@ -0,0 +1,167 @@
+//! Merkle inclusion proofs over a consensus round's validated transaction
+//! set, so light clients and pruned nodes can verify that a transaction
+//! was included in a round using only that round's stored root -- without
+//! holding the full DB.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::crypto::utils::sha256;
+
+/// Domain-separation prefix for leaf hashes, so a leaf hash can never
+/// collide with an internal pair-hash of the same bytes.
+const LEAF_PREFIX: &[u8] = b"SDUPI_MERKLE_LEAF";
+
+/// Domain-separation prefix for internal pair-hashes.
+const NODE_PREFIX: &[u8] = b"SDUPI_MERKLE_NODE";
+
+/// One step of an inclusion proof: a sibling hash plus which side it sits
+/// on relative to the node being proven at that level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Sibling hash at this level of the tree
+    pub sibling: [u8; 32],
+    /// `true` if `sibling` is the right-hand node at this level
+    pub sibling_is_right: bool,
+}
+
+/// Ordered sibling path proving a transaction ID's membership in a round's
+/// Merkle tree, from the leaf level up to the root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub steps: Vec<ProofStep>,
+}
+
+fn leaf_hash(id: &Uuid) -> [u8; 32] {
+    let mut data = LEAF_PREFIX.to_vec();
+    data.extend_from_slice(id.as_bytes());
+    to_array(sha256(&data))
+}
+
+fn pair_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut data = NODE_PREFIX.to_vec();
+    data.extend_from_slice(left);
+    data.extend_from_slice(right);
+    to_array(sha256(&data))
+}
+
+fn to_array(bytes: Vec<u8>) -> [u8; 32] {
+    let mut array = [0u8; 32];
+    array.copy_from_slice(&bytes);
+    array
+}
+
+/// Compute the Merkle root over `tx_ids`. IDs are sorted first so the root
+/// doesn't depend on validation order, and an odd level duplicates its
+/// last node before pairing, matching the canonical Bitcoin-style tree.
+pub fn compute_merkle_root(tx_ids: &[Uuid]) -> [u8; 32] {
+    if tx_ids.is_empty() {
+        return [0u8; 32];
+    }
+
+    let mut sorted = tx_ids.to_vec();
+    sorted.sort();
+    let mut level: Vec<[u8; 32]> = sorted.iter().map(leaf_hash).collect();
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+        level = level.chunks(2).map(|pair| pair_hash(&pair[0], &pair[1])).collect();
+    }
+
+    level[0]
+}
+
+/// Build an inclusion proof for `tx_id` within `tx_ids`'s Merkle tree, or
+/// `None` if `tx_id` isn't present in `tx_ids`.
+pub fn generate_proof(tx_ids: &[Uuid], tx_id: &Uuid) -> Option<MerkleProof> {
+    let mut sorted = tx_ids.to_vec();
+    sorted.sort();
+    let mut index = sorted.iter().position(|id| id == tx_id)?;
+
+    let mut level: Vec<[u8; 32]> = sorted.iter().map(leaf_hash).collect();
+    let mut steps = Vec::new();
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+
+        let sibling_is_right = index % 2 == 0;
+        let sibling_index = if sibling_is_right { index + 1 } else { index - 1 };
+        steps.push(ProofStep {
+            sibling: level[sibling_index],
+            sibling_is_right,
+        });
+
+        level = level.chunks(2).map(|pair| pair_hash(&pair[0], &pair[1])).collect();
+        index /= 2;
+    }
+
+    Some(MerkleProof { steps })
+}
+
+/// Recompute the root implied by `proof` for `tx_id` and check it matches
+/// `root`. Static verification: callers only need the round's stored root,
+/// not the full validated-transaction set.
+pub fn verify_inclusion_proof(root: &[u8; 32], tx_id: &Uuid, proof: &MerkleProof) -> bool {
+    let mut current = leaf_hash(tx_id);
+    for step in &proof.steps {
+        current = if step.sibling_is_right {
+            pair_hash(&current, &step.sibling)
+        } else {
+            pair_hash(&step.sibling, &current)
+        };
+    }
+    current == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_transaction_proof_verifies_against_its_own_leaf_hash() {
+        let id = Uuid::new_v4();
+        let root = compute_merkle_root(&[id]);
+        let proof = generate_proof(&[id], &id).unwrap();
+        assert!(proof.steps.is_empty());
+        assert!(verify_inclusion_proof(&root, &id, &proof));
+    }
+
+    #[test]
+    fn test_proof_verifies_for_every_member_of_an_odd_sized_set() {
+        let ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        let root = compute_merkle_root(&ids);
+
+        for id in &ids {
+            let proof = generate_proof(&ids, id).unwrap();
+            assert!(verify_inclusion_proof(&root, id, &proof));
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_transaction_not_in_the_set() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        assert!(generate_proof(&ids, &Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn test_proof_fails_verification_against_a_different_root() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let other_ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+
+        let other_root = compute_merkle_root(&other_ids);
+        let proof = generate_proof(&ids, &ids[0]).unwrap();
+        assert!(!verify_inclusion_proof(&other_root, &ids[0], &proof));
+    }
+
+    #[test]
+    fn test_root_is_independent_of_validation_order() {
+        let ids: Vec<Uuid> = (0..6).map(|_| Uuid::new_v4()).collect();
+        let mut shuffled = ids.clone();
+        shuffled.reverse();
+        assert_eq!(compute_merkle_root(&ids), compute_merkle_root(&shuffled));
+    }
+}
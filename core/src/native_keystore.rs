@@ -0,0 +1,184 @@
+//! BIP39-seeded ed25519 keystore for SDUPI-native wallets.
+//!
+//! Generates a BIP39 mnemonic, derives an ed25519 keypair from it along a
+//! configurable derivation path, and persists the resulting secret key
+//! encrypted at rest: a passphrase is stretched into a 32-byte key with
+//! Argon2, and the secret key is sealed with ChaCha20-Poly1305 under a
+//! fresh 12-byte nonce into an [`AccountBackup`].
+
+use crate::crypto::KeyPair;
+use crate::error::SDUPIError;
+use argon2::Argon2;
+use chacha20poly1305::{aead::Aead, ChaCha20Poly1305, Key, KeyInit, Nonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Default SDUPI-native HD derivation path, following the BIP44 pattern
+/// (`coin_type` 601 is unassigned in SLIP-44 and reserved here for SDUPI).
+pub const DEFAULT_DERIVATION_PATH: &str = "m/44'/601'/0'/0'";
+
+/// An encrypted-at-rest ed25519 secret key, plus what's needed to open it
+/// again: the Argon2 salt and the ChaCha20-Poly1305 nonce. Safe to persist
+/// or transmit -- `ciphertext` only opens with the original passphrase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBackup {
+    pub salt: [u8; 16],
+    pub nonce: [u8; 12],
+    pub ciphertext: Vec<u8>,
+    pub derivation_path: String,
+}
+
+/// Manages BIP39-seeded ed25519 keypairs for SDUPI-native wallets: mnemonic
+/// generation, HD-style derivation, and encrypted backup/restore.
+#[derive(Debug, Clone)]
+pub struct NativeKeyStore {
+    derivation_path: String,
+}
+
+impl Default for NativeKeyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NativeKeyStore {
+    /// Creates a keystore that derives along [`DEFAULT_DERIVATION_PATH`].
+    pub fn new() -> Self {
+        Self {
+            derivation_path: DEFAULT_DERIVATION_PATH.to_string(),
+        }
+    }
+
+    /// Uses a custom derivation path instead of the default.
+    pub fn with_derivation_path(mut self, path: impl Into<String>) -> Self {
+        self.derivation_path = path.into();
+        self
+    }
+
+    /// Generates a fresh 24-word BIP39 mnemonic and derives its ed25519
+    /// keypair. The mnemonic is returned once and never stored -- the
+    /// caller is responsible for recording it.
+    pub fn create_wallet(&self) -> Result<(bip39::Mnemonic, KeyPair), SDUPIError> {
+        let mnemonic = bip39::Mnemonic::generate(24)
+            .map_err(|e| SDUPIError::Crypto(format!("failed to generate BIP39 mnemonic: {}", e)))?;
+        let keypair = self.derive_keypair(&mnemonic, "")?;
+        Ok((mnemonic, keypair))
+    }
+
+    /// Re-derives a wallet's keypair from its BIP39 mnemonic phrase.
+    pub fn restore_from_mnemonic(&self, phrase: &str, passphrase: &str) -> Result<KeyPair, SDUPIError> {
+        let mnemonic = bip39::Mnemonic::parse(phrase)
+            .map_err(|e| SDUPIError::Crypto(format!("invalid BIP39 mnemonic: {}", e)))?;
+        self.derive_keypair(&mnemonic, passphrase)
+    }
+
+    /// Encrypts `keypair`'s secret key at rest: stretches `passphrase` into
+    /// a 32-byte key with Argon2 under a fresh random salt, then seals the
+    /// secret key with ChaCha20-Poly1305 under a fresh random nonce.
+    pub fn export_backup(&self, keypair: &KeyPair, passphrase: &str) -> Result<AccountBackup, SDUPIError> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let encryption_key = derive_encryption_key(passphrase, &salt)?;
+
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&encryption_key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), keypair.secret_key_bytes().expose_secret().as_ref())
+            .map_err(|e| SDUPIError::Crypto(format!("failed to seal account backup: {}", e)))?;
+
+        Ok(AccountBackup {
+            salt,
+            nonce: nonce_bytes,
+            ciphertext,
+            derivation_path: self.derivation_path.clone(),
+        })
+    }
+
+    /// Decrypts an [`AccountBackup`] back into its ed25519 keypair. Fails
+    /// if `passphrase` is wrong or the ciphertext was tampered with.
+    pub fn import_backup(&self, backup: &AccountBackup, passphrase: &str) -> Result<KeyPair, SDUPIError> {
+        let encryption_key = derive_encryption_key(passphrase, &backup.salt)?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&encryption_key));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&backup.nonce), backup.ciphertext.as_ref())
+            .map_err(|_| {
+                SDUPIError::Crypto("failed to open account backup: wrong passphrase or tampered data".to_string())
+            })?;
+        KeyPair::from_secret_key_bytes(&plaintext)
+    }
+
+    /// Folds a BIP39 seed and the configured derivation path into a 32-byte
+    /// ed25519 secret key.
+    ///
+    /// This crate doesn't wire in real SLIP-0010 ed25519 HD derivation yet
+    /// -- tweaking ed25519 points for hardened child keys needs its own
+    /// implementation beyond what `ed25519_dalek` exposes. Hashing the path
+    /// into the seed at least keeps distinct paths off a single mnemonic
+    /// deterministic and independent, which is enough to exercise the
+    /// generate/restore/backup bookkeeping this subsystem exists for.
+    fn derive_keypair(&self, mnemonic: &bip39::Mnemonic, passphrase: &str) -> Result<KeyPair, SDUPIError> {
+        let seed = mnemonic.to_seed(passphrase);
+        let mut hasher = Sha256::new();
+        hasher.update(seed);
+        hasher.update(self.derivation_path.as_bytes());
+        let secret_key_bytes: [u8; 32] = hasher.finalize().into();
+        KeyPair::from_secret_key_bytes(&secret_key_bytes)
+    }
+}
+
+fn derive_encryption_key(passphrase: &str, salt: &[u8; 16]) -> Result<[u8; 32], SDUPIError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| SDUPIError::Crypto(format!("passphrase key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_wallet_mnemonic_restores_same_keypair() {
+        let keystore = NativeKeyStore::new();
+        let (mnemonic, original) = keystore.create_wallet().unwrap();
+
+        let restored = keystore.restore_from_mnemonic(&mnemonic.to_string(), "").unwrap();
+        assert_eq!(original.public_key().to_bytes(), restored.public_key().to_bytes());
+    }
+
+    #[test]
+    fn test_different_derivation_paths_yield_different_keys() {
+        let (mnemonic, default_keypair) = NativeKeyStore::new().create_wallet().unwrap();
+        let alt_keystore = NativeKeyStore::new().with_derivation_path("m/44'/601'/1'/0'");
+        let alt_keypair = alt_keystore.restore_from_mnemonic(&mnemonic.to_string(), "").unwrap();
+
+        assert_ne!(
+            default_keypair.public_key().to_bytes(),
+            alt_keypair.public_key().to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_export_import_backup_round_trips_secret_key() {
+        let keystore = NativeKeyStore::new();
+        let (_, keypair) = keystore.create_wallet().unwrap();
+
+        let backup = keystore.export_backup(&keypair, "correct horse battery staple").unwrap();
+        let restored = keystore.import_backup(&backup, "correct horse battery staple").unwrap();
+
+        assert_eq!(keypair.public_key().to_bytes(), restored.public_key().to_bytes());
+    }
+
+    #[test]
+    fn test_import_backup_rejects_wrong_passphrase() {
+        let keystore = NativeKeyStore::new();
+        let (_, keypair) = keystore.create_wallet().unwrap();
+
+        let backup = keystore.export_backup(&keypair, "correct horse battery staple").unwrap();
+        assert!(keystore.import_backup(&backup, "wrong passphrase").is_err());
+    }
+}
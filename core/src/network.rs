@@ -1,13 +1,27 @@
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use prometheus::{Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry};
 use libp2p::{
-    core::upgrade,
-    floodsub::{Floodsub, FloodsubEvent, FloodsubMessage, Topic},
+    core::{upgrade, ProtocolName},
+    gossipsub::{
+        Gossipsub, GossipsubConfigBuilder, GossipsubEvent, GossipsubMessage, IdentTopic as Topic,
+        MessageAcceptance, MessageAuthenticity, MessageId, PeerScoreParams, PeerScoreThresholds,
+        ValidationMode,
+    },
     identity,
     mdns::{Mdns, MdnsEvent},
-    swarm::{NetworkBehaviourEventProcess, Swarm},
+    multiaddr::{Multiaddr, Protocol},
+    rendezvous::{Cookie, Namespace, Rendezvous, RendezvousEvent},
+    request_response::{
+        ProtocolSupport, RequestId, RequestResponse, RequestResponseCodec, RequestResponseConfig,
+        RequestResponseEvent, RequestResponseMessage, ResponseChannel,
+    },
+    swarm::{ConnectionLimits, NetworkBehaviourEventProcess, Swarm, SwarmBuilder, SwarmEvent},
     tcp::TokioTcpConfig,
     Transport, PeerId,
 };
@@ -17,6 +31,88 @@ use crate::transaction::Transaction;
 use crate::dag::DAGLedger;
 use crate::SDUPIError;
 
+/// Reputation deltas applied for observed peer behavior. Tuned so a
+/// handful of malformed messages bans a peer while a single lapse does
+/// not, and so honest relaying/syncing slowly earns back headroom.
+const REPUTATION_VALID_RELAY: i32 = 2;
+const REPUTATION_INVALID_MESSAGE: i32 = -10;
+const REPUTATION_HEARTBEAT_TIMEOUT: i32 = -5;
+const REPUTATION_SYNC_SUCCESS: i32 = 3;
+const REPUTATION_SYNC_FAILURE: i32 = -2;
+
+/// Point-to-point DAG sync protocol: `sync_from_peer` opens a substream to
+/// one chosen peer to backfill `DAGLedger` without flooding the `sync`
+/// gossipsub topic for what is normally a one-on-one catch-up exchange.
+#[derive(Debug, Clone, Default)]
+pub struct DagSyncProtocol;
+
+impl ProtocolName for DagSyncProtocol {
+    fn protocol_name(&self) -> &[u8] {
+        b"/sdupi/dag-sync/1.0.0"
+    }
+}
+
+/// A page of transactions requested starting after `from_transaction`
+/// (`None` means "from the beginning"), capped at `limit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRequest {
+    pub from_transaction: Option<Uuid>,
+    pub limit: usize,
+}
+
+/// The requested page, plus whether more transactions remain beyond it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncResponse {
+    pub transactions: Vec<Transaction>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DagSyncCodec;
+
+#[async_trait::async_trait]
+impl RequestResponseCodec for DagSyncCodec {
+    type Protocol = DagSyncProtocol;
+    type Request = SyncRequest;
+    type Response = SyncResponse;
+
+    async fn read_request<T>(&mut self, _: &DagSyncProtocol, io: &mut T) -> io::Result<Self::Request>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut buf = Vec::new();
+        io.read_to_end(&mut buf).await?;
+        serde_json::from_slice(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    async fn read_response<T>(&mut self, _: &DagSyncProtocol, io: &mut T) -> io::Result<Self::Response>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut buf = Vec::new();
+        io.read_to_end(&mut buf).await?;
+        serde_json::from_slice(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    async fn write_request<T>(&mut self, _: &DagSyncProtocol, io: &mut T, request: Self::Request) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        let data = serde_json::to_vec(&request).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        io.write_all(&data).await?;
+        io.close().await
+    }
+
+    async fn write_response<T>(&mut self, _: &DagSyncProtocol, io: &mut T, response: Self::Response) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        let data = serde_json::to_vec(&response).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        io.write_all(&data).await?;
+        io.close().await
+    }
+}
+
 /// Network message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum NetworkMessage {
@@ -70,21 +166,283 @@ pub enum NetworkMessage {
     },
 }
 
+/// Derive a deterministic message ID from a `NetworkMessage`'s raw bytes so
+/// gossipsub recognizes re-broadcasts of the same payload as duplicates to
+/// dedupe instead of re-flooding them through the mesh.
+fn message_id_fn(message: &GossipsubMessage) -> MessageId {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    message.data.hash(&mut hasher);
+    MessageId::from(hasher.finish().to_be_bytes().to_vec())
+}
+
+/// Prometheus metrics for the p2p layer. Registered once per node and
+/// shared (via `Arc`) between the behaviour, the actor, and the public
+/// `NodeNetwork` handle, so `metrics_handle` can hand the registry to an
+/// HTTP scrape endpoint without the network layer caring how it's served.
+pub struct NetworkMetrics {
+    registry: Registry,
+    connected_peers: IntGauge,
+    messages_published: IntCounterVec,
+    messages_received: IntCounterVec,
+    messages_rejected: IntCounter,
+    messages_duplicate: IntCounter,
+    bytes_sent: IntCounter,
+    bytes_received: IntCounter,
+    sync_requests_served: IntCounter,
+    sync_requests_sent: IntCounter,
+    sync_round_trip: Histogram,
+}
+
+fn metrics_err(e: prometheus::Error) -> SDUPIError {
+    SDUPIError::Network(format!("Failed to register network metric: {}", e))
+}
+
+impl NetworkMetrics {
+    fn new() -> Result<Self, SDUPIError> {
+        let registry = Registry::new();
+
+        let connected_peers = IntGauge::new(
+            "sdupi_network_connected_peers",
+            "Currently connected peers",
+        ).map_err(metrics_err)?;
+        let messages_published = IntCounterVec::new(
+            Opts::new("sdupi_network_messages_published_total", "Gossipsub messages published, by topic"),
+            &["topic"],
+        ).map_err(metrics_err)?;
+        let messages_received = IntCounterVec::new(
+            Opts::new("sdupi_network_messages_received_total", "Gossipsub messages received, by topic"),
+            &["topic"],
+        ).map_err(metrics_err)?;
+        let messages_rejected = IntCounter::new(
+            "sdupi_network_messages_rejected_total",
+            "Gossipsub messages rejected during validation",
+        ).map_err(metrics_err)?;
+        let messages_duplicate = IntCounter::new(
+            "sdupi_network_messages_duplicate_total",
+            "Gossipsub messages ignored as duplicates",
+        ).map_err(metrics_err)?;
+        let bytes_sent = IntCounter::new(
+            "sdupi_network_bytes_sent_total",
+            "Bytes published over gossipsub",
+        ).map_err(metrics_err)?;
+        let bytes_received = IntCounter::new(
+            "sdupi_network_bytes_received_total",
+            "Bytes received over gossipsub",
+        ).map_err(metrics_err)?;
+        let sync_requests_served = IntCounter::new(
+            "sdupi_network_sync_requests_served_total",
+            "Inbound DAG sync requests served",
+        ).map_err(metrics_err)?;
+        let sync_requests_sent = IntCounter::new(
+            "sdupi_network_sync_requests_sent_total",
+            "Outbound DAG sync requests issued",
+        ).map_err(metrics_err)?;
+        let sync_round_trip = Histogram::with_opts(HistogramOpts::new(
+            "sdupi_network_sync_round_trip_seconds",
+            "DAG sync request round-trip latency",
+        )).map_err(metrics_err)?;
+
+        registry.register(Box::new(connected_peers.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(messages_published.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(messages_received.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(messages_rejected.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(messages_duplicate.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(bytes_sent.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(bytes_received.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(sync_requests_served.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(sync_requests_sent.clone())).map_err(metrics_err)?;
+        registry.register(Box::new(sync_round_trip.clone())).map_err(metrics_err)?;
+
+        Ok(Self {
+            registry,
+            connected_peers,
+            messages_published,
+            messages_received,
+            messages_rejected,
+            messages_duplicate,
+            bytes_sent,
+            bytes_received,
+            sync_requests_served,
+            sync_requests_sent,
+            sync_round_trip,
+        })
+    }
+}
+
 /// Network behavior for SDUPI nodes
 #[derive(NetworkBehaviour)]
 pub struct SDUPINetworkBehaviour {
-    /// Floodsub for message broadcasting
-    floodsub: Floodsub,
-    
+    /// Gossipsub for mesh-based message propagation with validation and
+    /// peer scoring, replacing the all-peers-flooding `Floodsub`
+    gossipsub: Gossipsub,
+
     /// mDNS for local peer discovery
     mdns: Mdns,
+
+    /// Point-to-point request/response protocol for `DAGLedger` backfill,
+    /// used instead of flooding the `sync` gossipsub topic
+    dag_sync: RequestResponse<DagSyncCodec>,
+
+    /// Rendezvous protocol client: registers this node, and discovers
+    /// peers others have registered, under `rendezvous_namespace` at each
+    /// configured rendezvous point — finds peers beyond the local mDNS
+    /// broadcast domain
+    rendezvous: Rendezvous,
+
+    /// Transaction IDs already seen, so a duplicate broadcast of the same
+    /// transaction is reported `Ignore` rather than re-validated
+    #[behaviour(ignore)]
+    seen_transactions: HashSet<Uuid>,
+
+    /// Ledger queried to answer inbound `SyncRequest`s
+    #[behaviour(ignore)]
+    dag_ledger: Arc<DAGLedger>,
+
+    /// Outstanding `sync_from_peer` calls awaiting their response, keyed by
+    /// the `RequestId` returned from `dag_sync.send_request`
+    #[behaviour(ignore)]
+    pending_sync_requests: Arc<Mutex<HashMap<RequestId, oneshot::Sender<SyncResponse>>>>,
+
+    /// Shared with `NodeNetwork`, so observed behavior here is reflected in
+    /// `NodeNetwork::reputation`/`ban_peer`
+    #[behaviour(ignore)]
+    peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
+
+    /// Peers queued for banning by `NetworkActor::run` because their score
+    /// (tracked in `peers`) fell below `min_reputation`
+    #[behaviour(ignore)]
+    ban_requests: Arc<Mutex<HashSet<PeerId>>>,
+
+    /// Reputation floor below which a peer is queued for banning
+    #[behaviour(ignore)]
+    min_reputation: i32,
+
+    /// Namespace this node registers/discovers under at rendezvous points
+    #[behaviour(ignore)]
+    rendezvous_namespace: Namespace,
+
+    /// Pagination cookie from the most recent successful `discover`, passed
+    /// to the next call so results aren't re-delivered from the start
+    #[behaviour(ignore)]
+    rendezvous_cookie: Arc<Mutex<Option<Cookie>>>,
+
+    /// Peers and addresses learned via rendezvous discovery, shared with
+    /// `NodeNetwork::list_peers_in_namespace`
+    #[behaviour(ignore)]
+    discovered_peers: Arc<RwLock<HashMap<PeerId, Multiaddr>>>,
+
+    /// Addresses `inject_event` wants dialed, drained by the swarm-owning
+    /// task after each poll (an `inject_event` callback has no direct way
+    /// to issue a dial itself)
+    #[behaviour(ignore)]
+    dial_requests: Arc<Mutex<Vec<Multiaddr>>>,
+
+    /// Prometheus counters/gauges/histogram for the p2p layer, shared with
+    /// `NodeNetwork::metrics_handle`
+    #[behaviour(ignore)]
+    metrics: Arc<NetworkMetrics>,
+
+    /// When each outstanding outbound sync request was sent, so the
+    /// matching `Message::Response` can observe its round-trip latency
+    #[behaviour(ignore)]
+    sync_request_started: Arc<Mutex<HashMap<RequestId, Instant>>>,
+
+    /// 4-byte magic prefixed on every outbound message and checked on every
+    /// inbound one, so peers on a different `--network` cannot accidentally
+    /// handshake with this one (see `SDUPIError::NetworkMismatch`)
+    #[behaviour(ignore)]
+    network_magic: [u8; 4],
+}
+
+impl NetworkBehaviourEventProcess<GossipsubEvent> for SDUPINetworkBehaviour {
+    fn inject_event(&mut self, event: GossipsubEvent) {
+        if let GossipsubEvent::Message { propagation_source, message_id, message } = event {
+            let acceptance = self.handle_gossipsub_message(&propagation_source, &message);
+            if let Err(e) = self.gossipsub.report_message_validation_result(&message_id, &propagation_source, acceptance) {
+                tracing::warn!("Failed to report gossipsub validation result: {}", e);
+            }
+        }
+    }
+}
+
+impl NetworkBehaviourEventProcess<RequestResponseEvent<SyncRequest, SyncResponse>> for SDUPINetworkBehaviour {
+    fn inject_event(&mut self, event: RequestResponseEvent<SyncRequest, SyncResponse>) {
+        match event {
+            RequestResponseEvent::Message { peer, message } => match message {
+                RequestResponseMessage::Request { request, channel, .. } => {
+                    self.metrics.sync_requests_served.inc();
+                    let (transactions, has_more) = self
+                        .dag_ledger
+                        .transactions_after(request.from_transaction, request.limit)
+                        .unwrap_or_else(|e| {
+                            tracing::warn!("Failed to page DAG ledger for sync request from {}: {}", peer, e);
+                            (Vec::new(), false)
+                        });
+
+                    let response = SyncResponse { transactions, has_more };
+                    if self.dag_sync.send_response(channel, response).is_err() {
+                        tracing::warn!("Failed to send DAG sync response to {}", peer);
+                    }
+                }
+                RequestResponseMessage::Response { request_id, response } => {
+                    self.adjust_reputation(&peer, REPUTATION_SYNC_SUCCESS);
+                    self.observe_sync_round_trip(&request_id);
+                    let sender = self.pending_sync_requests.lock()
+                        .ok()
+                        .and_then(|mut pending| pending.remove(&request_id));
+                    if let Some(sender) = sender {
+                        let _ = sender.send(response);
+                    }
+                }
+            },
+            RequestResponseEvent::OutboundFailure { peer, request_id, error, .. } => {
+                tracing::warn!("DAG sync request to {} failed: {:?}", peer, error);
+                self.adjust_reputation(&peer, REPUTATION_SYNC_FAILURE);
+                self.observe_sync_round_trip(&request_id);
+                if let Ok(mut pending) = self.pending_sync_requests.lock() {
+                    pending.remove(&request_id);
+                }
+            }
+            RequestResponseEvent::InboundFailure { peer, error, .. } => {
+                tracing::warn!("DAG sync request from {} failed: {:?}", peer, error);
+                self.adjust_reputation(&peer, REPUTATION_SYNC_FAILURE);
+            }
+            RequestResponseEvent::ResponseSent { .. } => {}
+        }
+    }
 }
 
-impl NetworkBehaviourEventProcess<FloodsubEvent> for SDUPINetworkBehaviour {
-    fn inject_event(&mut self, event: FloodsubEvent) {
-        if let FloodsubEvent::Message(message) = event {
-            // Handle incoming floodsub messages
-            self.handle_floodsub_message(message);
+impl NetworkBehaviourEventProcess<RendezvousEvent> for SDUPINetworkBehaviour {
+    fn inject_event(&mut self, event: RendezvousEvent) {
+        match event {
+            RendezvousEvent::Discovered { registrations, cookie, .. } => {
+                if let Ok(mut discovered) = self.discovered_peers.write() {
+                    for registration in &registrations {
+                        if let Some(addr) = registration.record.addresses().first() {
+                            discovered.insert(registration.record.peer_id(), addr.clone());
+                            if let Ok(mut dials) = self.dial_requests.lock() {
+                                dials.push(addr.clone());
+                            }
+                        }
+                    }
+                }
+                if let Ok(mut stored_cookie) = self.rendezvous_cookie.lock() {
+                    *stored_cookie = Some(cookie);
+                }
+                tracing::debug!("Discovered {} peer(s) via rendezvous", registrations.len());
+            }
+            RendezvousEvent::DiscoverFailed { rendezvous_node, namespace, error } => {
+                tracing::warn!("Rendezvous discover at {} for {:?} failed: {:?}", rendezvous_node, namespace, error);
+            }
+            RendezvousEvent::Registered { rendezvous_node, namespace, .. } => {
+                tracing::info!("Registered under namespace {:?} at rendezvous point {}", namespace, rendezvous_node);
+            }
+            RendezvousEvent::RegisterFailed(error) => {
+                tracing::warn!("Rendezvous registration failed: {:?}", error);
+            }
+            other => {
+                tracing::debug!("Rendezvous event: {:?}", other);
+            }
         }
     }
 }
@@ -94,13 +452,13 @@ impl NetworkBehaviourEventProcess<MdnsEvent> for SDUPINetworkBehaviour {
         match event {
             MdnsEvent::Discovered(list) => {
                 for (peer_id, _) in list {
-                    self.floodsub.add_node_to_partial_view(peer_id);
+                    self.gossipsub.add_explicit_peer(&peer_id);
                 }
             }
             MdnsEvent::Expired(list) => {
                 for (peer_id, _) in list {
                     if !self.mdns.has_node(&peer_id) {
-                        self.floodsub.remove_node_from_partial_view(&peer_id);
+                        self.gossipsub.remove_explicit_peer(&peer_id);
                     }
                 }
             }
@@ -110,76 +468,352 @@ impl NetworkBehaviourEventProcess<MdnsEvent> for SDUPINetworkBehaviour {
 
 impl SDUPINetworkBehaviour {
     /// Create new network behavior
-    pub fn new(peer_id: PeerId) -> Self {
+    pub fn new(
+        local_key: &identity::Keypair,
+        peer_id: PeerId,
+        dag_ledger: Arc<DAGLedger>,
+        pending_sync_requests: Arc<Mutex<HashMap<RequestId, oneshot::Sender<SyncResponse>>>>,
+        peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
+        ban_requests: Arc<Mutex<HashSet<PeerId>>>,
+        min_reputation: i32,
+        rendezvous_namespace: String,
+        discovered_peers: Arc<RwLock<HashMap<PeerId, Multiaddr>>>,
+        metrics: Arc<NetworkMetrics>,
+        network_magic: [u8; 4],
+    ) -> Self {
+        let gossipsub_config = GossipsubConfigBuilder::default()
+            .validation_mode(ValidationMode::Strict)
+            .validate_messages()
+            .message_id_fn(message_id_fn)
+            .build()
+            .expect("valid gossipsub config");
+
+        let mut gossipsub = Gossipsub::new(MessageAuthenticity::Signed(local_key.clone()), gossipsub_config)
+            .expect("valid gossipsub behaviour");
+
+        gossipsub.with_peer_score(PeerScoreParams::default(), PeerScoreThresholds::default())
+            .expect("valid peer score parameters");
+
+        let dag_sync = RequestResponse::new(
+            DagSyncCodec::default(),
+            std::iter::once((DagSyncProtocol, ProtocolSupport::Full)),
+            RequestResponseConfig::default(),
+        );
+
+        let rendezvous_namespace = Namespace::new(rendezvous_namespace)
+            .expect("valid rendezvous namespace");
+
         let mut behaviour = Self {
-            floodsub: Floodsub::new(peer_id),
+            gossipsub,
             mdns: Mdns::new(Default::default()).expect("Failed to create mDNS"),
+            dag_sync,
+            rendezvous: Rendezvous::new(local_key.clone(), Default::default()),
+            seen_transactions: HashSet::new(),
+            dag_ledger,
+            pending_sync_requests,
+            peers,
+            ban_requests,
+            min_reputation,
+            rendezvous_namespace,
+            rendezvous_cookie: Arc::new(Mutex::new(None)),
+            discovered_peers,
+            dial_requests: Arc::new(Mutex::new(Vec::new())),
+            metrics,
+            sync_request_started: Arc::new(Mutex::new(HashMap::new())),
+            network_magic,
         };
-        
+
         // Subscribe to network topics
-        behaviour.floodsub.subscribe(Topic::new("transactions"));
-        behaviour.floodsub.subscribe(Topic::new("validation"));
-        behaviour.floodsub.subscribe(Topic::new("sync"));
-        behaviour.floodsub.subscribe(Topic::new("heartbeat"));
-        
+        for topic in ["transactions", "validation", "sync", "heartbeat"] {
+            if let Err(e) = behaviour.gossipsub.subscribe(&Topic::new(topic)) {
+                tracing::error!("Failed to subscribe to topic {}: {}", topic, e);
+            }
+        }
+
+        let _ = peer_id; // retained for API compatibility with callers that pass it explicitly
+
         behaviour
     }
-    
-    /// Handle incoming floodsub messages
-    fn handle_floodsub_message(&mut self, message: FloodsubMessage) {
-        // Parse and handle network messages
-        if let Ok(network_message) = serde_json::from_slice::<NetworkMessage>(&message.data) {
-            match network_message {
-                NetworkMessage::NewTransaction { transaction, .. } => {
-                    // Handle new transaction
-                    tracing::info!("Received new transaction: {}", transaction.id);
-                }
-                NetworkMessage::ValidationRequest { transaction_id, .. } => {
-                    // Handle validation request
-                    tracing::info!("Received validation request for: {}", transaction_id);
+
+    /// Validate an inbound gossipsub message and decide whether it should be
+    /// accepted (and thus re-propagated to the mesh), rejected (malformed or
+    /// a transaction that fails signature checks — penalizes the sender's
+    /// peer score), or ignored (a duplicate of one already seen).
+    fn handle_gossipsub_message(&mut self, source: &PeerId, message: &GossipsubMessage) -> MessageAcceptance {
+        let topic = message.topic.to_string();
+        self.metrics.messages_received.with_label_values(&[&topic]).inc();
+        self.metrics.bytes_received.inc_by(message.data.len() as u64);
+
+        if message.data.len() < 4 || message.data[..4] != self.network_magic[..] {
+            let got: [u8; 4] = message.data.get(..4)
+                .and_then(|s| s.try_into().ok())
+                .unwrap_or([0; 4]);
+            tracing::debug!(
+                "{}",
+                SDUPIError::NetworkMismatch(self.network_magic, got)
+            );
+            self.adjust_reputation(source, REPUTATION_INVALID_MESSAGE);
+            self.metrics.messages_rejected.inc();
+            return MessageAcceptance::Reject;
+        }
+
+        let network_message = match serde_json::from_slice::<NetworkMessage>(&message.data[4..]) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::debug!("Rejecting malformed gossipsub message: {}", e);
+                self.adjust_reputation(source, REPUTATION_INVALID_MESSAGE);
+                self.metrics.messages_rejected.inc();
+                return MessageAcceptance::Reject;
+            }
+        };
+
+        match network_message {
+            NetworkMessage::NewTransaction { transaction, .. } => {
+                if self.seen_transactions.contains(&transaction.id) {
+                    self.metrics.messages_duplicate.inc();
+                    return MessageAcceptance::Ignore;
                 }
-                NetworkMessage::Heartbeat { node_id, .. } => {
-                    // Handle heartbeat
-                    tracing::debug!("Received heartbeat from: {}", node_id);
+
+                if transaction.validate_structure().is_err() {
+                    tracing::debug!("Rejecting structurally invalid transaction: {}", transaction.id);
+                    self.adjust_reputation(source, REPUTATION_INVALID_MESSAGE);
+                    self.metrics.messages_rejected.inc();
+                    return MessageAcceptance::Reject;
                 }
-                _ => {
-                    // Handle other message types
-                    tracing::debug!("Received network message: {:?}", network_message);
+
+                let signature_ok = match &transaction.signature {
+                    Some(signature) => transaction.sender.verify(&transaction.hash(), signature).is_ok(),
+                    None => false,
+                };
+                if !signature_ok {
+                    tracing::debug!("Rejecting transaction with invalid signature: {}", transaction.id);
+                    self.adjust_reputation(source, REPUTATION_INVALID_MESSAGE);
+                    self.metrics.messages_rejected.inc();
+                    return MessageAcceptance::Reject;
                 }
+
+                self.seen_transactions.insert(transaction.id);
+                self.adjust_reputation(source, REPUTATION_VALID_RELAY);
+                tracing::info!("Accepted new transaction: {}", transaction.id);
+                MessageAcceptance::Accept
+            }
+            NetworkMessage::ValidationRequest { transaction_id, .. } => {
+                tracing::info!("Received validation request for: {}", transaction_id);
+                MessageAcceptance::Accept
+            }
+            NetworkMessage::Heartbeat { node_id, .. } => {
+                tracing::debug!("Received heartbeat from: {}", node_id);
+                MessageAcceptance::Accept
+            }
+            other => {
+                tracing::debug!("Received network message: {:?}", other);
+                MessageAcceptance::Accept
             }
         }
     }
-    
+
+    /// Adjust a peer's reputation score by `delta`, creating a tracked
+    /// entry with the default score if this is the first observation of
+    /// it. Queues the peer for banning (drained by `NetworkActor::run`) if
+    /// the score drops below `min_reputation` — this behaviour has no
+    /// direct handle on the swarm to disconnect it itself.
+    fn adjust_reputation(&self, peer_id: &PeerId, delta: i32) {
+        let mut peers = match self.peers.write() {
+            Ok(peers) => peers,
+            Err(_) => return,
+        };
+
+        let entry = peers.entry(*peer_id).or_insert_with(|| PeerInfo {
+            peer_id: *peer_id,
+            address: String::new(),
+            last_seen: Instant::now(),
+            is_connected: true,
+            node_type: NodeType::Full,
+            reputation: 0,
+        });
+        entry.reputation = entry.reputation.saturating_add(delta);
+        entry.last_seen = Instant::now();
+
+        if entry.reputation < self.min_reputation {
+            if let Ok(mut pending) = self.ban_requests.lock() {
+                pending.insert(*peer_id);
+            }
+        }
+    }
+
     /// Broadcast a message to the network
     pub fn broadcast(&mut self, topic: &str, message: &NetworkMessage) -> Result<(), SDUPIError> {
+        let topic_name = topic.to_string();
         let topic = Topic::new(topic);
-        let data = serde_json::to_vec(message)
-            .map_err(|e| SDUPIError::Serialization(format!("Failed to serialize message: {}", e)))?;
-        
-        self.floodsub.publish(topic, data);
+        let mut data = self.network_magic.to_vec();
+        data.extend(serde_json::to_vec(message)
+            .map_err(|e| SDUPIError::Serialization(format!("Failed to serialize message: {}", e)))?);
+
+        self.metrics.messages_published.with_label_values(&[&topic_name]).inc();
+        self.metrics.bytes_sent.inc_by(data.len() as u64);
+
+        self.gossipsub.publish(topic, data)
+            .map_err(|e| SDUPIError::Network(format!("Failed to publish gossipsub message: {}", e)))?;
         Ok(())
     }
+
+    /// Open a DAG sync substream to `peer`, returning the `RequestId` the
+    /// caller should key `pending_sync_requests` on to await the response.
+    pub fn request_sync(&mut self, peer: &PeerId, request: SyncRequest) -> RequestId {
+        let request_id = self.dag_sync.send_request(peer, request);
+        self.metrics.sync_requests_sent.inc();
+        if let Ok(mut started) = self.sync_request_started.lock() {
+            started.insert(request_id, Instant::now());
+        }
+        request_id
+    }
+
+    /// Record round-trip latency for a completed (successful or failed)
+    /// outbound sync request, if it's still tracked.
+    fn observe_sync_round_trip(&self, request_id: &RequestId) {
+        let started_at = self.sync_request_started.lock().ok()
+            .and_then(|mut started| started.remove(request_id));
+        if let Some(started_at) = started_at {
+            self.metrics.sync_round_trip.observe(started_at.elapsed().as_secs_f64());
+        }
+    }
+
+    /// Unregister this node from `rendezvous_point` under its configured
+    /// namespace, e.g. as part of a cooperative shutdown.
+    pub fn unregister_at(&mut self, rendezvous_point: PeerId) {
+        self.rendezvous.unregister(self.rendezvous_namespace.clone(), rendezvous_point);
+    }
+
+    /// Register this node under `rendezvous_namespace` at `rendezvous_point`.
+    pub fn register_at(&mut self, rendezvous_point: PeerId) {
+        self.rendezvous.register(self.rendezvous_namespace.clone(), rendezvous_point, None);
+    }
+
+    /// Ask `rendezvous_point` for peers registered under
+    /// `rendezvous_namespace`, continuing from the last `Cookie` if one was
+    /// returned by a prior call so results aren't re-delivered.
+    pub fn discover_at(&mut self, rendezvous_point: PeerId) {
+        let cookie = self.rendezvous_cookie.lock().ok().and_then(|c| c.clone());
+        self.rendezvous.discover(Some(self.rendezvous_namespace.clone()), cookie, None, rendezvous_point);
+    }
+
+    /// Drain addresses queued for dialing by `inject_event` handlers (e.g.
+    /// newly rendezvous-discovered peers) — `inject_event` has no direct
+    /// way to issue a dial itself, so the swarm-owning task must do it.
+    pub fn drain_dial_requests(&self) -> Vec<Multiaddr> {
+        self.dial_requests.lock()
+            .map(|mut pending| pending.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    /// Snapshot of peers learned via rendezvous discovery.
+    pub fn discovered_peers_snapshot(&self) -> Vec<(PeerId, Multiaddr)> {
+        self.discovered_peers.read()
+            .map(|peers| peers.iter().map(|(id, addr)| (*id, addr.clone())).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Commands sent over `NodeNetwork`'s command channel to the single task
+/// that owns the `Swarm`. `Swarm` is not `Clone`, so this is the only way
+/// the public, freely-cloneable `NodeNetwork` handle can drive it.
+pub enum NetworkCommand {
+    BroadcastTransaction {
+        transaction: Box<Transaction>,
+        reply: oneshot::Sender<Result<(), SDUPIError>>,
+    },
+    RequestValidation {
+        transaction_id: Uuid,
+        validator: String,
+        reply: oneshot::Sender<Result<(), SDUPIError>>,
+    },
+    SyncFromPeer {
+        peer_id: PeerId,
+        from: Option<Uuid>,
+        limit: usize,
+        reply: oneshot::Sender<Result<SyncResponse, SDUPIError>>,
+    },
+    DialPeer {
+        addr: Multiaddr,
+        reply: oneshot::Sender<Result<(), SDUPIError>>,
+    },
+    GetStats {
+        reply: oneshot::Sender<NetworkStats>,
+    },
+    BanPeer {
+        peer_id: PeerId,
+        reply: oneshot::Sender<()>,
+    },
+    /// Cooperatively drain and stop the actor: unregister from every
+    /// configured rendezvous point, disconnect all connected peers, then
+    /// let `run` return instead of looping forever.
+    Shutdown {
+        reply: oneshot::Sender<()>,
+    },
 }
 
-/// Network node for SDUPI blockchain
+/// Capacity of the command channel: bounded so a burst of calls applies
+/// backpressure to callers instead of the actor loop falling behind and
+/// buffering unboundedly.
+const COMMAND_CHANNEL_CAPACITY: usize = 256;
+
+/// Network node for SDUPI blockchain. A thin, cloneable handle: the
+/// `Swarm` itself lives in exactly one task (`NetworkActor::run`, spawned
+/// by `new`) and is driven over `command_sender`, never cloned.
 pub struct NodeNetwork {
     /// Local peer ID
     peer_id: PeerId,
-    
-    /// Network swarm
-    swarm: Swarm<SDUPINetworkBehaviour>,
-    
-    /// DAG ledger reference
-    dag_ledger: Arc<DAGLedger>,
-    
-    /// Connected peers
+
+    /// Sender half of the command channel driving the actor task
+    command_sender: mpsc::Sender<NetworkCommand>,
+
+    /// Join handle for the actor task, awaited by `run`
+    actor_handle: tokio::task::JoinHandle<()>,
+
+    /// Connected peers — shared with the actor so reads here (e.g.
+    /// `reputation`) don't need to round-trip through the command channel
     peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
-    
-    /// Message sender for async processing
-    message_sender: mpsc::UnboundedSender<NetworkMessage>,
-    
+
     /// Network configuration
     config: NetworkConfig,
+
+    /// Currently banned peers and when their ban expires
+    bans: Arc<RwLock<HashMap<PeerId, Instant>>>,
+
+    /// Peers and addresses learned via rendezvous discovery
+    discovered_peers: Arc<RwLock<HashMap<PeerId, Multiaddr>>>,
+
+    /// Prometheus registry for the p2p layer, shared with the actor
+    metrics: Arc<NetworkMetrics>,
+}
+
+/// Owns the `Swarm` and all the state that used to be scattered across
+/// `NodeNetwork`'s several independently-cloned-`Swarm` background tasks;
+/// one `tokio::select!` loop multiplexes commands, swarm events, and
+/// timers so libp2p I/O happens in exactly one place.
+struct NetworkActor {
+    peer_id: PeerId,
+    swarm: Swarm<SDUPINetworkBehaviour>,
+    peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
+    bans: Arc<RwLock<HashMap<PeerId, Instant>>>,
+    ban_requests: Arc<Mutex<HashSet<PeerId>>>,
+    pending_sync_requests: Arc<Mutex<HashMap<RequestId, oneshot::Sender<SyncResponse>>>>,
+    config: NetworkConfig,
+    command_receiver: mpsc::Receiver<NetworkCommand>,
+    metrics: Arc<NetworkMetrics>,
+    /// Rendezvous points resolved to `(PeerId, Multiaddr)` once at
+    /// construction, reused by both the discovery tick and shutdown
+    rendezvous_points: Vec<(PeerId, Multiaddr)>,
+}
+
+/// Extract the `PeerId` from a `/p2p/<peer id>`-suffixed multiaddr, as used
+/// for rendezvous points and other addresses where the remote identity is
+/// known ahead of the dial.
+fn peer_id_from_multiaddr(addr: &Multiaddr) -> Option<PeerId> {
+    addr.iter().find_map(|proto| match proto {
+        Protocol::P2p(hash) => PeerId::from_multihash(hash).ok(),
+        _ => None,
+    })
 }
 
 /// Peer information
@@ -196,9 +830,14 @@ pub struct PeerInfo {
     
     /// Connection status
     pub is_connected: bool,
-    
+
     /// Node type (full/light)
     pub node_type: NodeType,
+
+    /// Reputation score, adjusted by observed behavior (see the
+    /// `REPUTATION_*` constants); peers below `NetworkConfig::min_reputation`
+    /// are banned
+    pub reputation: i32,
 }
 
 /// Node types
@@ -225,6 +864,27 @@ pub struct NetworkConfig {
     
     /// Max peers
     pub max_peers: usize,
+
+    /// Reputation score (see `REPUTATION_*`) below which a peer is
+    /// disconnected and banned
+    pub min_reputation: i32,
+
+    /// How long a banned peer is refused reconnection
+    pub ban_duration: Duration,
+
+    /// Rendezvous points (reachable at `/p2p/<peer id>`-suffixed addresses)
+    /// used to find peers beyond the local mDNS broadcast domain
+    pub rendezvous_points: Vec<Multiaddr>,
+
+    /// Namespace this node registers/discovers under at each rendezvous
+    /// point, e.g. `"sdupi-full-nodes"`
+    pub rendezvous_namespace: String,
+
+    /// 4-byte magic identifying the network this node belongs to (set from
+    /// `ConsensusParams::magic` for the network selected with `--network`),
+    /// prefixed on every wire message so peers on other networks are
+    /// rejected instead of accidentally handshaking
+    pub network_magic: [u8; 4],
 }
 
 impl Default for NetworkConfig {
@@ -235,12 +895,19 @@ impl Default for NetworkConfig {
             heartbeat_interval: Duration::from_secs(30),
             connection_timeout: Duration::from_secs(10),
             max_peers: 50,
+            min_reputation: -20,
+            ban_duration: Duration::from_secs(600),
+            rendezvous_points: Vec::new(),
+            rendezvous_namespace: "sdupi-full-nodes".to_string(),
+            network_magic: [0x53, 0x44, 0x55, 0x01], // mainnet magic
         }
     }
 }
 
 impl NodeNetwork {
-    /// Create a new network node
+    /// Create a new network node. The `Swarm` is handed to a single
+    /// spawned `NetworkActor` task and never touched again from here —
+    /// every other method is a thin command-channel sender.
     pub async fn new(
         dag_ledger: Arc<DAGLedger>,
         config: NetworkConfig,
@@ -248,7 +915,7 @@ impl NodeNetwork {
         // Generate local peer identity
         let local_key = identity::Keypair::generate_ed25519();
         let peer_id = PeerId::from(local_key.public());
-        
+
         // Create transport
         let transport = TokioTcpConfig::new()
             .nodelay(true)
@@ -256,184 +923,475 @@ impl NodeNetwork {
             .authenticate(libp2p::noise::NoiseAuthenticated::xx(&local_key).unwrap())
             .multiplex(libp2p::yamux::YamuxConfig::default())
             .boxed();
-        
+
         // Create network behavior
-        let behaviour = SDUPINetworkBehaviour::new(peer_id);
-        
-        // Create swarm
-        let mut swarm = Swarm::new(transport, behaviour, peer_id);
-        
+        let pending_sync_requests = Arc::new(Mutex::new(HashMap::new()));
+        let peers = Arc::new(RwLock::new(HashMap::new()));
+        let ban_requests = Arc::new(Mutex::new(HashSet::new()));
+        let discovered_peers = Arc::new(RwLock::new(HashMap::new()));
+        let bans = Arc::new(RwLock::new(HashMap::new()));
+        let metrics = Arc::new(NetworkMetrics::new()?);
+        let behaviour = SDUPINetworkBehaviour::new(
+            &local_key,
+            peer_id,
+            dag_ledger,
+            pending_sync_requests.clone(),
+            peers.clone(),
+            ban_requests.clone(),
+            config.min_reputation,
+            config.rendezvous_namespace.clone(),
+            discovered_peers.clone(),
+            metrics.clone(),
+            config.network_magic,
+        );
+
+        // Create swarm, capping established/pending connections so a flood
+        // of dials can't exceed `config.max_peers` before reputation-based
+        // eviction even has a chance to run
+        let connection_limits = ConnectionLimits::default()
+            .with_max_established(Some(config.max_peers as u32))
+            .with_max_established_per_peer(Some(1));
+        let mut swarm = SwarmBuilder::new(transport, behaviour, peer_id)
+            .connection_limits(connection_limits)
+            .build();
+
         // Listen on address
         swarm.listen_on(config.listen_addr.parse()
             .map_err(|e| SDUPIError::Network(format!("Invalid listen address: {}", e)))?)?;
-        
-        // Create message channel
-        let (message_sender, mut message_receiver) = mpsc::unbounded_channel();
-        
-        // Spawn message handler
-        let swarm_clone = swarm.clone();
-        tokio::spawn(async move {
-            while let Some(message) = message_receiver.recv().await {
-                // Handle incoming messages
-                Self::handle_network_message(&swarm_clone, message).await;
-            }
-        });
-        
-        Ok(Self {
+
+        let (command_sender, command_receiver) = mpsc::channel(COMMAND_CHANNEL_CAPACITY);
+
+        let rendezvous_points: Vec<(PeerId, Multiaddr)> = config.rendezvous_points.iter()
+            .filter_map(|addr| peer_id_from_multiaddr(addr).map(|peer_id| (peer_id, addr.clone())))
+            .collect();
+
+        let actor = NetworkActor {
             peer_id,
             swarm,
-            dag_ledger,
-            peers: Arc::new(RwLock::new(HashMap::new())),
-            message_sender,
+            peers: peers.clone(),
+            bans: bans.clone(),
+            ban_requests,
+            pending_sync_requests,
+            config: config.clone(),
+            command_receiver,
+            metrics: metrics.clone(),
+            rendezvous_points,
+        };
+        let actor_handle = tokio::spawn(actor.run());
+
+        Ok(Self {
+            peer_id,
+            command_sender,
+            actor_handle,
+            peers,
             config,
+            bans,
+            discovered_peers,
+            metrics,
         })
     }
-    
-    /// Start the network node
+
+    /// Start the network node. Heartbeat and peer discovery now run
+    /// unconditionally inside the actor loop, so this is kept only for API
+    /// compatibility with callers that await it before `run`.
     pub async fn start(&mut self) -> Result<(), SDUPIError> {
         tracing::info!("Starting SDUPI network node: {}", self.peer_id);
-        
-        // Start heartbeat
-        self.start_heartbeat().await?;
-        
-        // Start peer discovery
-        self.start_peer_discovery().await?;
-        
         Ok(())
     }
-    
-    /// Start heartbeat mechanism
-    async fn start_heartbeat(&self) -> Result<(), SDUPIError> {
-        let message_sender = self.message_sender.clone();
-        let node_id = self.peer_id.to_string();
-        let interval = self.config.heartbeat_interval;
-        
-        tokio::spawn(async move {
-            let mut interval_timer = tokio::time::interval(interval);
-            let start_time = Instant::now();
-            
-            loop {
-                interval_timer.tick().await;
-                
-                let uptime = start_time.elapsed().as_secs();
-                let heartbeat = NetworkMessage::Heartbeat {
-                    node_id: node_id.clone(),
-                    timestamp: chrono::Utc::now().timestamp() as u64,
-                    uptime,
-                };
-                
-                if let Err(e) = message_sender.send(heartbeat) {
-                    tracing::error!("Failed to send heartbeat: {}", e);
+
+    /// Prometheus registry for this node's p2p metrics — connected peer
+    /// count, per-topic gossip traffic, validation outcomes, byte counters,
+    /// sync request counts, and sync round-trip latency — for a caller to
+    /// mount behind an HTTP scrape endpoint.
+    pub fn metrics_handle(&self) -> Registry {
+        self.metrics.registry.clone()
+    }
+
+    /// Cooperatively stop the network actor: it unregisters from every
+    /// configured rendezvous point, disconnects all connected peers, then
+    /// returns, after which `run` resolves instead of blocking forever.
+    pub async fn shutdown(&self) -> Result<(), SDUPIError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.send_command(NetworkCommand::Shutdown { reply }).await?;
+        reply_rx.await.map_err(|_| SDUPIError::Network("Network actor dropped reply".to_string()))
+    }
+
+    /// Peers discovered under `config.rendezvous_namespace` so far, as
+    /// `(peer id, multiaddr)` pairs — the same kind of listing one might
+    /// use to enumerate sellers registered in a marketplace namespace.
+    pub fn list_peers_in_namespace(&self) -> Vec<(PeerId, Multiaddr)> {
+        self.discovered_peers.read()
+            .map(|peers| peers.iter().map(|(id, addr)| (*id, addr.clone())).collect())
+            .unwrap_or_default()
+    }
+
+    /// Broadcast new transaction
+    pub async fn broadcast_transaction(&self, transaction: &Transaction) -> Result<(), SDUPIError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.send_command(NetworkCommand::BroadcastTransaction {
+            transaction: Box::new(transaction.clone()),
+            reply,
+        }).await?;
+        reply_rx.await.map_err(|_| SDUPIError::Network("Network actor dropped reply".to_string()))?
+    }
+
+    /// Request transaction validation
+    pub async fn request_validation(&self, transaction_id: Uuid, validator: &str) -> Result<(), SDUPIError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.send_command(NetworkCommand::RequestValidation {
+            transaction_id,
+            validator: validator.to_string(),
+            reply,
+        }).await?;
+        reply_rx.await.map_err(|_| SDUPIError::Network("Network actor dropped reply".to_string()))?
+    }
+
+    /// Backfill the DAG from `peer`, requesting a page of transactions after
+    /// `from` (or from the beginning if `None`), capped at `limit`. Opens a
+    /// dedicated `dag_sync` substream rather than broadcasting over gossipsub,
+    /// since this is a one-on-one catch-up exchange.
+    pub async fn sync_from_peer(
+        &self,
+        peer_id: PeerId,
+        from: Option<Uuid>,
+        limit: usize,
+    ) -> Result<SyncResponse, SDUPIError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.send_command(NetworkCommand::SyncFromPeer { peer_id, from, limit, reply }).await?;
+        reply_rx.await.map_err(|_| SDUPIError::Network("Network actor dropped reply".to_string()))?
+    }
+
+    /// Dial an address directly, outside of mDNS/rendezvous discovery.
+    pub async fn dial_peer(&self, addr: Multiaddr) -> Result<(), SDUPIError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.send_command(NetworkCommand::DialPeer { addr, reply }).await?;
+        reply_rx.await.map_err(|_| SDUPIError::Network("Network actor dropped reply".to_string()))?
+    }
+
+    /// Get network statistics
+    pub async fn get_statistics(&self) -> Result<NetworkStats, SDUPIError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.send_command(NetworkCommand::GetStats { reply }).await?;
+        reply_rx.await.map_err(|_| SDUPIError::Network("Network actor dropped reply".to_string()))
+    }
+
+    /// Ban `peer_id` for `config.ban_duration`, disconnecting it
+    /// immediately; reconnection attempts are refused until the ban
+    /// expires. The actual disconnect happens in the actor task, since
+    /// only it holds the `Swarm`.
+    pub async fn ban_peer(&self, peer_id: &PeerId) -> Result<(), SDUPIError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.send_command(NetworkCommand::BanPeer { peer_id: *peer_id, reply }).await?;
+        reply_rx.await.map_err(|_| SDUPIError::Network("Network actor dropped reply".to_string()))
+    }
+
+    /// Lift a ban before its natural expiry. Pure bookkeeping on the
+    /// `bans` map shared with the actor — no swarm access needed.
+    pub fn unban_peer(&self, peer_id: &PeerId) {
+        if let Ok(mut bans) = self.bans.write() {
+            bans.remove(peer_id);
+        }
+    }
+
+    /// Current reputation score for a tracked peer, if any.
+    pub fn reputation(&self, peer_id: &PeerId) -> Option<i32> {
+        self.peers.read().ok()?.get(peer_id).map(|info| info.reputation)
+    }
+
+    /// Block until the actor task exits (on `shutdown`, Ctrl-C, or a
+    /// panic), surfacing a panic as an error rather than propagating it.
+    pub async fn run(&mut self) -> Result<(), SDUPIError> {
+        (&mut self.actor_handle).await
+            .map_err(|e| SDUPIError::Network(format!("Network actor task panicked: {}", e)))
+    }
+
+    async fn send_command(&self, command: NetworkCommand) -> Result<(), SDUPIError> {
+        self.command_sender.send(command).await
+            .map_err(|_| SDUPIError::Network("Network actor channel closed".to_string()))
+    }
+}
+
+impl NetworkActor {
+    /// Single owning loop for the `Swarm`: multiplexes inbound commands,
+    /// swarm/behaviour events, heartbeat, and rendezvous discovery so none
+    /// of them need their own `Swarm` clone.
+    async fn run(mut self) {
+        let mut heartbeat_timer = tokio::time::interval(self.config.heartbeat_interval);
+        let start_time = Instant::now();
+        let stale_after = self.config.heartbeat_interval * 2;
+        let mut rendezvous_registered = self.rendezvous_points.is_empty();
+
+        loop {
+            tokio::select! {
+                command = self.command_receiver.recv() => {
+                    match command {
+                        Some(NetworkCommand::Shutdown { reply }) => {
+                            tracing::info!("Shutdown requested for network actor {}", self.peer_id);
+                            self.drain_and_disconnect();
+                            let _ = reply.send(());
+                            break;
+                        }
+                        Some(command) => self.handle_command(command),
+                        None => {
+                            tracing::info!("Command channel closed; stopping network actor for {}", self.peer_id);
+                            break;
+                        }
+                    }
+                }
+                event = self.swarm.next_event() => {
+                    if let Some(event) = event {
+                        self.handle_swarm_event(event);
+                    }
+                }
+                _ = heartbeat_timer.tick() => {
+                    self.send_heartbeat(start_time.elapsed().as_secs());
+                    self.penalize_stale_peers(stale_after);
+                    let rendezvous_points = self.rendezvous_points.clone();
+                    for (peer_id, addr) in &rendezvous_points {
+                        if !rendezvous_registered {
+                            if let Err(e) = self.swarm.dial(addr.clone()) {
+                                tracing::warn!("Failed to dial rendezvous point {}: {}", peer_id, e);
+                                continue;
+                            }
+                            self.swarm.register_at(*peer_id);
+                        }
+                        self.swarm.discover_at(*peer_id);
+                    }
+                    rendezvous_registered = true;
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("Received Ctrl-C; shutting down network actor for {}", self.peer_id);
+                    self.drain_and_disconnect();
+                    break;
                 }
             }
-        });
-        
-        Ok(())
+
+            for addr in self.swarm.drain_dial_requests() {
+                if let Err(e) = self.swarm.dial(addr.clone()) {
+                    tracing::debug!("Failed to dial rendezvous-discovered peer at {}: {}", addr, e);
+                }
+            }
+            self.drain_ban_requests();
+        }
+
+        tracing::info!("Network actor for {} has stopped", self.peer_id);
     }
-    
-    /// Start peer discovery
-    async fn start_peer_discovery(&self) -> Result<(), SDUPIError> {
-        let message_sender = self.message_sender.clone();
-        let node_id = self.peer_id.to_string();
-        let interval = Duration::from_secs(60); // Discover peers every minute
-        
-        tokio::spawn(async move {
-            let mut interval_timer = tokio::time::interval(interval);
-            
-            loop {
-                interval_timer.tick().await;
-                
-                let discovery = NetworkMessage::PeerDiscovery {
-                    node_id: node_id.clone(),
-                    peers: Vec::new(), // TODO: Get actual peer list
+
+    /// Cooperative shutdown: unregister from every rendezvous point this
+    /// node registered at, then disconnect every currently-connected peer
+    /// so remotes see a clean close rather than a dropped connection.
+    fn drain_and_disconnect(&mut self) {
+        for (peer_id, _) in self.rendezvous_points.clone() {
+            self.swarm.unregister_at(peer_id);
+        }
+
+        let connected: Vec<PeerId> = self.peers.read()
+            .map(|peers| peers.values().filter(|p| p.is_connected).map(|p| p.peer_id).collect())
+            .unwrap_or_default();
+        for peer_id in connected {
+            let _ = self.swarm.disconnect_peer_id(peer_id);
+        }
+    }
+
+    fn handle_command(&mut self, command: NetworkCommand) {
+        match command {
+            NetworkCommand::BroadcastTransaction { transaction, reply } => {
+                let message = NetworkMessage::NewTransaction {
+                    transaction: *transaction,
+                    timestamp: chrono::Utc::now().timestamp() as u64,
+                };
+                let _ = reply.send(self.swarm.broadcast("transactions", &message));
+            }
+            NetworkCommand::RequestValidation { transaction_id, validator, reply } => {
+                let message = NetworkMessage::ValidationRequest {
+                    transaction_id,
+                    validator,
                     timestamp: chrono::Utc::now().timestamp() as u64,
                 };
-                
-                if let Err(e) = message_sender.send(discovery) {
-                    tracing::error!("Failed to send peer discovery: {}", e);
+                let _ = reply.send(self.swarm.broadcast("validation", &message));
+            }
+            NetworkCommand::SyncFromPeer { peer_id, from, limit, reply } => {
+                let request_id = self.swarm.request_sync(&peer_id, SyncRequest { from_transaction: from, limit });
+                let (inner_tx, inner_rx) = oneshot::channel();
+                if let Ok(mut pending) = self.pending_sync_requests.lock() {
+                    pending.insert(request_id, inner_tx);
                 }
+                let timeout = self.config.connection_timeout;
+                tokio::spawn(async move {
+                    let result = match tokio::time::timeout(timeout, inner_rx).await {
+                        Ok(Ok(response)) => Ok(response),
+                        Ok(Err(_)) => Err(SDUPIError::Network(format!("DAG sync request to {} was dropped", peer_id))),
+                        Err(_) => Err(SDUPIError::Network(format!("DAG sync request to {} timed out", peer_id))),
+                    };
+                    let _ = reply.send(result);
+                });
             }
-        });
-        
-        Ok(())
-    }
-    
-    /// Handle network message
-    async fn handle_network_message(
-        swarm: &Swarm<SDUPINetworkBehaviour>,
-        message: NetworkMessage,
-    ) {
-        match message {
-            NetworkMessage::NewTransaction { transaction, .. } => {
-                // Process new transaction
-                tracing::info!("Processing new transaction: {}", transaction.id);
+            NetworkCommand::DialPeer { addr, reply } => {
+                let result = self.swarm.dial(addr)
+                    .map_err(|e| SDUPIError::Network(format!("Failed to dial peer: {}", e)));
+                let _ = reply.send(result);
+            }
+            NetworkCommand::GetStats { reply } => {
+                let stats = self.peers.read().map(|peers| NetworkStats {
+                    peer_id: self.peer_id.to_string(),
+                    total_peers: peers.len(),
+                    connected_peers: peers.values().filter(|p| p.is_connected).count(),
+                    node_type: NodeType::Full,
+                }).unwrap_or(NetworkStats {
+                    peer_id: self.peer_id.to_string(),
+                    total_peers: 0,
+                    connected_peers: 0,
+                    node_type: NodeType::Full,
+                });
+                let _ = reply.send(stats);
+            }
+            NetworkCommand::BanPeer { peer_id, reply } => {
+                self.ban_peer(&peer_id);
+                let _ = reply.send(());
             }
-            NetworkMessage::ValidationRequest { transaction_id, validator, .. } => {
-                // Process validation request
-                tracing::info!("Processing validation request from {} for {}", validator, transaction_id);
+        }
+    }
+
+    fn handle_swarm_event<E: std::fmt::Debug>(&mut self, event: SwarmEvent<(), E>) {
+        match event {
+            SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+                if self.is_banned(&peer_id) {
+                    tracing::debug!("Refusing connection from banned peer {}", peer_id);
+                    let _ = self.swarm.disconnect_peer_id(peer_id);
+                } else {
+                    self.mark_peer_connected(peer_id);
+                    self.enforce_peer_limit();
+                }
             }
-            NetworkMessage::DAGSyncRequest { from_transaction, limit, .. } => {
-                // Process DAG sync request
-                tracing::info!("Processing DAG sync request from {:?}, limit: {}", from_transaction, limit);
+            SwarmEvent::ConnectionClosed { peer_id, .. } => {
+                if let Ok(mut peers) = self.peers.write() {
+                    if let Some(info) = peers.get_mut(&peer_id) {
+                        info.is_connected = false;
+                        info.last_seen = Instant::now();
+                    }
+                }
+                self.refresh_connected_peers_gauge();
             }
-            _ => {
-                // Handle other message types
-                tracing::debug!("Processing network message: {:?}", message);
+            other => {
+                tracing::debug!("Swarm event: {:?}", other);
             }
         }
     }
-    
-    /// Broadcast new transaction
-    pub async fn broadcast_transaction(&self, transaction: &Transaction) -> Result<(), SDUPIError> {
-        let message = NetworkMessage::NewTransaction {
-            transaction: transaction.clone(),
+
+    fn send_heartbeat(&mut self, uptime: u64) {
+        let message = NetworkMessage::Heartbeat {
+            node_id: self.peer_id.to_string(),
             timestamp: chrono::Utc::now().timestamp() as u64,
+            uptime,
         };
-        
-        self.message_sender.send(message)
-            .map_err(|e| SDUPIError::Network(format!("Failed to send message: {}", e)))?;
-        
-        Ok(())
+        if let Err(e) = self.swarm.broadcast("heartbeat", &message) {
+            tracing::error!("Failed to send heartbeat: {}", e);
+        }
     }
-    
-    /// Request transaction validation
-    pub async fn request_validation(&self, transaction_id: Uuid, validator: &str) -> Result<(), SDUPIError> {
-        let message = NetworkMessage::ValidationRequest {
-            transaction_id,
-            validator: validator.to_string(),
-            timestamp: chrono::Utc::now().timestamp() as u64,
-        };
-        
-        self.message_sender.send(message)
-            .map_err(|e| SDUPIError::Network(format!("Failed to send message: {}", e)))?;
-        
-        Ok(())
+
+    /// Penalize connected peers that have gone quiet for more than
+    /// `stale_after`, queuing them for banning if that pushes their score
+    /// below the configured floor.
+    fn penalize_stale_peers(&mut self, stale_after: Duration) {
+        let Ok(mut peers) = self.peers.write() else { return };
+        for info in peers.values_mut().filter(|p| p.is_connected) {
+            if info.last_seen.elapsed() > stale_after {
+                info.reputation = info.reputation.saturating_add(REPUTATION_HEARTBEAT_TIMEOUT);
+                if info.reputation < self.config.min_reputation {
+                    if let Ok(mut pending) = self.ban_requests.lock() {
+                        pending.insert(info.peer_id);
+                    }
+                }
+            }
+        }
     }
-    
-    /// Get network statistics
-    pub fn get_statistics(&self) -> Result<NetworkStats, SDUPIError> {
-        let peers = self.peers.read()
-            .map_err(|_| SDUPIError::Storage("Failed to acquire read lock".to_string()))?;
-        
-        Ok(NetworkStats {
-            peer_id: self.peer_id.to_string(),
-            total_peers: peers.len(),
-            connected_peers: peers.values().filter(|p| p.is_connected).count(),
-            node_type: NodeType::Full, // TODO: Make configurable
-        })
+
+    /// Record that `peer_id` is now connected, creating a tracked entry
+    /// with the default reputation if this is the first time it's been
+    /// seen.
+    fn mark_peer_connected(&self, peer_id: PeerId) {
+        if let Ok(mut peers) = self.peers.write() {
+            let entry = peers.entry(peer_id).or_insert_with(|| PeerInfo {
+                peer_id,
+                address: String::new(),
+                last_seen: Instant::now(),
+                is_connected: true,
+                node_type: NodeType::Full,
+                reputation: 0,
+            });
+            entry.is_connected = true;
+            entry.last_seen = Instant::now();
+        }
+        self.refresh_connected_peers_gauge();
     }
-    
-    /// Run network event loop
-    pub async fn run(&mut self) -> Result<(), SDUPIError> {
-        loop {
-            tokio::select! {
-                swarm_event = self.swarm.next_event() => {
-                    if let Some(event) = swarm_event {
-                        // Handle swarm events
-                        tracing::debug!("Swarm event: {:?}", event);
-                    }
+
+    /// Sync the `connected_peers` gauge with the current connected count in
+    /// `self.peers`, called after anything that changes connection state.
+    fn refresh_connected_peers_gauge(&self) {
+        let connected = self.peers.read()
+            .map(|peers| peers.values().filter(|p| p.is_connected).count())
+            .unwrap_or(0);
+        self.metrics.connected_peers.set(connected as i64);
+    }
+
+    /// Evict the lowest-scoring connected peer if the connected count now
+    /// exceeds `config.max_peers`, since `ConnectionLimits` bounds pending
+    /// and established connections but doesn't know about reputation.
+    fn enforce_peer_limit(&mut self) {
+        let victim = self.peers.read().ok().and_then(|peers| {
+            if peers.values().filter(|p| p.is_connected).count() <= self.config.max_peers {
+                return None;
+            }
+            peers.values()
+                .filter(|p| p.is_connected)
+                .min_by_key(|p| p.reputation)
+                .map(|p| p.peer_id)
+        });
+
+        if let Some(peer_id) = victim {
+            tracing::info!("Evicting lowest-scoring peer {} to stay within max_peers", peer_id);
+            let _ = self.swarm.disconnect_peer_id(peer_id);
+            if let Ok(mut peers) = self.peers.write() {
+                if let Some(info) = peers.get_mut(&peer_id) {
+                    info.is_connected = false;
                 }
             }
+            self.refresh_connected_peers_gauge();
+        }
+    }
+
+    /// Ban `peer_id` for `config.ban_duration`, disconnecting it
+    /// immediately; reconnection attempts are refused until the ban
+    /// expires (see `is_banned`).
+    fn ban_peer(&mut self, peer_id: &PeerId) {
+        if let Ok(mut bans) = self.bans.write() {
+            bans.insert(*peer_id, Instant::now() + self.config.ban_duration);
+        }
+        if let Ok(mut peers) = self.peers.write() {
+            if let Some(info) = peers.get_mut(peer_id) {
+                info.is_connected = false;
+            }
+        }
+        let _ = self.swarm.disconnect_peer_id(*peer_id);
+        self.refresh_connected_peers_gauge();
+    }
+
+    /// Whether `peer_id` is currently serving an unexpired ban.
+    fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.bans.read()
+            .map(|bans| bans.get(peer_id).map(|expiry| Instant::now() < *expiry).unwrap_or(false))
+            .unwrap_or(false)
+    }
+
+    /// Drain peers `SDUPINetworkBehaviour` queued for banning because
+    /// their reputation fell below `config.min_reputation`.
+    fn drain_ban_requests(&mut self) {
+        let to_ban: Vec<PeerId> = self.ban_requests.lock()
+            .map(|mut pending| pending.drain().collect())
+            .unwrap_or_default();
+        for peer_id in to_ban {
+            tracing::warn!("Peer {} reputation fell below threshold; banning", peer_id);
+            self.ban_peer(&peer_id);
         }
     }
 }
@@ -468,6 +1426,7 @@ mod tests {
             last_seen: Instant::now(),
             is_connected: true,
             node_type: NodeType::Full,
+            reputation: 0,
         };
         
         assert_eq!(peer_info.peer_id, peer_id);
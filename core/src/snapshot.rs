@@ -0,0 +1,180 @@
+//! Snapshot export/import for fast node bootstrap.
+//!
+//! Instead of replaying the full DAG history, a new validator can fetch a
+//! [`LedgerSnapshot`] (finalized transactions, derived account balances and
+//! the current tip set) as a series of fixed-size, content-addressed chunks
+//! described by a [`ChunkManifest`]. Each chunk is verified against the
+//! manifest before it is applied, and manifests whose chunks fail
+//! verification are blacklisted so a corrupt snapshot source isn't retried
+//! forever.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::crypto::utils::sha256;
+use crate::storage::LedgerStore;
+use crate::transaction::{Transaction, TransactionStatus};
+use crate::SDUPIError;
+
+/// Default size, in bytes, of a single snapshot chunk.
+pub const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// The ledger state captured by a snapshot.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LedgerSnapshot {
+    /// Current DAG tips at the time the snapshot was taken.
+    pub tips: Vec<Uuid>,
+    /// All finalized (confirmed) transactions.
+    pub transactions: Vec<Transaction>,
+    /// Account balances derived from `transactions`, keyed by hex-encoded
+    /// public key. Signed because this ledger has no genesis allocation, so
+    /// a partial transaction history can show a sender as net-negative.
+    pub balances: HashMap<String, i128>,
+}
+
+/// Manifest describing a snapshot's chunks, so a receiver can verify each
+/// chunk independently before assembling and applying the snapshot.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChunkManifest {
+    /// Hash committing to the full ordered set of chunk hashes.
+    pub state_root: String,
+    /// Chunk size used to split the serialized snapshot.
+    pub chunk_size: usize,
+    /// Hex-encoded SHA-256 hash of each chunk, in order.
+    pub chunk_hashes: Vec<String>,
+}
+
+/// A snapshot ready to be shipped to a syncing peer: the manifest plus the
+/// chunk bytes it describes.
+#[derive(Debug, Clone)]
+pub struct SnapshotExport {
+    pub manifest: ChunkManifest,
+    pub chunks: Vec<Vec<u8>>,
+}
+
+/// Build a [`LedgerSnapshot`] from a store's confirmed transactions and
+/// split it into content-addressed chunks described by a [`ChunkManifest`].
+pub fn export_snapshot(store: &dyn LedgerStore, chunk_size: usize) -> Result<SnapshotExport, SDUPIError> {
+    let tips = store.get_tips()?;
+    let transactions: Vec<Transaction> = store
+        .get_all_transactions()?
+        .into_iter()
+        .filter(|tx| tx.status == TransactionStatus::Confirmed)
+        .collect();
+
+    let mut balances: HashMap<String, i128> = HashMap::new();
+    for tx in &transactions {
+        let sender = hex::encode(tx.sender.to_bytes());
+        let recipient = hex::encode(tx.recipient.to_bytes());
+        *balances.entry(sender).or_insert(0) -= (tx.amount + tx.fee) as i128;
+        *balances.entry(recipient).or_insert(0) += tx.amount as i128;
+    }
+
+    let snapshot = LedgerSnapshot { tips, transactions, balances };
+    let serialized = bincode::serialize(&snapshot)?;
+
+    let chunk_size = chunk_size.max(1);
+    let chunks: Vec<Vec<u8>> = serialized.chunks(chunk_size).map(|c| c.to_vec()).collect();
+    let chunk_hashes: Vec<String> = chunks.iter().map(|c| hex::encode(sha256(c))).collect();
+    let state_root = compute_state_root(&chunk_hashes);
+
+    Ok(SnapshotExport {
+        manifest: ChunkManifest { state_root, chunk_size, chunk_hashes },
+        chunks,
+    })
+}
+
+/// Verify `chunks` against `manifest` and, if every chunk and the state
+/// root check out, reassemble and deserialize the [`LedgerSnapshot`].
+///
+/// On any verification failure, `manifest.state_root` is recorded in the
+/// blacklist at `blacklist_path` so a corrupt snapshot source isn't
+/// retried endlessly by future import attempts.
+pub fn import_snapshot(
+    manifest: &ChunkManifest,
+    chunks: &[Vec<u8>],
+    blacklist_path: &Path,
+) -> Result<LedgerSnapshot, SDUPIError> {
+    let mut blacklist = load_blacklist(blacklist_path)?;
+    if blacklist.contains(&manifest.state_root) {
+        return Err(SDUPIError::Storage(format!(
+            "Snapshot manifest {} is blacklisted after a previous verification failure",
+            manifest.state_root
+        )));
+    }
+
+    if let Err(err) = verify_chunks(manifest, chunks) {
+        blacklist.insert(manifest.state_root.clone());
+        save_blacklist(blacklist_path, &blacklist)?;
+        return Err(err);
+    }
+
+    let mut serialized = Vec::with_capacity(chunks.iter().map(Vec::len).sum());
+    for chunk in chunks {
+        serialized.extend_from_slice(chunk);
+    }
+    let snapshot: LedgerSnapshot = bincode::deserialize(&serialized)?;
+    Ok(snapshot)
+}
+
+/// Write a verified [`LedgerSnapshot`] into `store`, so a new node can
+/// bootstrap from it instead of replaying the full DAG history.
+pub fn apply_snapshot(store: &dyn LedgerStore, snapshot: &LedgerSnapshot) -> Result<(), SDUPIError> {
+    for tx in &snapshot.transactions {
+        store.store_transaction(tx)?;
+    }
+    for tip in &snapshot.tips {
+        store.store_tip(tip)?;
+    }
+    store.flush()
+}
+
+fn verify_chunks(manifest: &ChunkManifest, chunks: &[Vec<u8>]) -> Result<(), SDUPIError> {
+    if chunks.len() != manifest.chunk_hashes.len() {
+        return Err(SDUPIError::Storage(format!(
+            "Snapshot chunk count mismatch: manifest expects {}, got {}",
+            manifest.chunk_hashes.len(),
+            chunks.len()
+        )));
+    }
+
+    for (index, (chunk, expected_hash)) in chunks.iter().zip(manifest.chunk_hashes.iter()).enumerate() {
+        let actual_hash = hex::encode(sha256(chunk));
+        if &actual_hash != expected_hash {
+            return Err(SDUPIError::Storage(format!(
+                "Snapshot chunk {} failed verification: expected hash {}, got {}",
+                index, expected_hash, actual_hash
+            )));
+        }
+    }
+
+    let recomputed_root = compute_state_root(&manifest.chunk_hashes);
+    if recomputed_root != manifest.state_root {
+        return Err(SDUPIError::Storage(
+            "Snapshot state root does not match its chunk hashes".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn compute_state_root(chunk_hashes: &[String]) -> String {
+    hex::encode(sha256(chunk_hashes.join("").as_bytes()))
+}
+
+fn load_blacklist(path: &Path) -> Result<HashSet<String>, SDUPIError> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+    let data = std::fs::read_to_string(path)?;
+    let entries: Vec<String> = serde_json::from_str(&data)?;
+    Ok(entries.into_iter().collect())
+}
+
+fn save_blacklist(path: &Path, blacklist: &HashSet<String>) -> Result<(), SDUPIError> {
+    let entries: Vec<&String> = blacklist.iter().collect();
+    let data = serde_json::to_string_pretty(&entries)?;
+    std::fs::write(path, data)?;
+    Ok(())
+}
@@ -8,37 +8,266 @@ use std::sync::{Arc, RwLock};
 use wasmtime::{Engine, Store, Module, Instance, Linker};
 use wasmtime_wasi::WasiCtxBuilder;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 use async_trait::async_trait;
 
+/// Class of WASM opcodes used to weight gas costs differently than raw
+/// wasmtime fuel (which charges every instruction 1 unit regardless of cost).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OpcodeClass {
+    /// Linear memory loads/stores, memory.grow, memory.copy, etc.
+    Memory,
+    /// Direct/indirect calls
+    Call,
+    /// Integer/float arithmetic and comparisons
+    Arithmetic,
+    /// Everything else (control flow, locals, constants)
+    Other,
+}
+
 /// WASM VM configuration
 #[derive(Debug, Clone)]
 pub struct WASMConfig {
     /// Maximum memory size in bytes
     pub max_memory_size: usize,
-    
+
     /// Maximum execution time in seconds
     pub max_execution_time: u64,
-    
+
     /// Maximum stack size
     pub max_stack_size: usize,
-    
+
     /// Enable WASI support
     pub enable_wasi: bool,
-    
+
     /// Enable neural network support
     pub enable_nn: bool,
+
+    /// Per-opcode-class gas cost, used to weight raw wasmtime fuel consumption
+    /// into a charge that reflects real execution cost (memory ops and calls
+    /// are priced higher than simple arithmetic).
+    pub gas_cost_table: HashMap<OpcodeClass, u64>,
+
+    /// Maximum number of functions (imported + defined) a module may declare
+    pub max_functions: usize,
+
+    /// Maximum number of globals a module may declare
+    pub max_globals: usize,
+
+    /// Maximum number of table entries a module may declare
+    pub max_table_entries: usize,
 }
 
 impl Default for WASMConfig {
     fn default() -> Self {
+        let mut gas_cost_table = HashMap::new();
+        gas_cost_table.insert(OpcodeClass::Memory, 8);
+        gas_cost_table.insert(OpcodeClass::Call, 16);
+        gas_cost_table.insert(OpcodeClass::Arithmetic, 1);
+        gas_cost_table.insert(OpcodeClass::Other, 1);
+
         Self {
             max_memory_size: 64 * 1024 * 1024, // 64MB
             max_execution_time: 30, // 30 seconds
             max_stack_size: 1024 * 1024, // 1MB
             enable_wasi: true,
             enable_nn: false,
+            gas_cost_table,
+            max_functions: 512,
+            max_globals: 64,
+            max_table_entries: 1024,
+        }
+    }
+}
+
+/// One function's own opcode-class tally plus the (direct-call) function
+/// indices it statically invokes, gathered by [`GasMeteringProfile::analyze`]
+/// before the call-graph rollup that produces the exported multipliers.
+#[derive(Debug, Clone, Default)]
+struct FunctionProfile {
+    own_weighted: u64,
+    own_count: u64,
+    /// Direct-call targets (`call`, not `call_indirect` -- its target isn't
+    /// known statically, so it's priced as `OpcodeClass::Call` in the
+    /// caller's own tally and not followed as a graph edge).
+    callees: std::collections::HashSet<u32>,
+}
+
+/// Per-function weighted gas multiplier, computed once at deploy time by
+/// statically walking the module's code section with `wasmparser` and
+/// pricing each opcode through `WASMConfig::gas_cost_table`. Raw fuel
+/// consumed during execution (which wasmtime charges at 1 unit/instruction)
+/// is scaled by this multiplier to approximate the weighted cost of the
+/// instructions that ran.
+///
+/// This is deliberately *not* the block-level bytecode instrumentation a
+/// fully weighted metering scheme needs -- splitting every function into
+/// metered blocks at branch/loop/call boundaries and injecting a trapping
+/// `gas(u64)` host call at each block head, so the weighted cost is charged
+/// as it is incurred rather than approximated. That requires a module
+/// encoder/rewriter this crate doesn't have (the same kind of gap the
+/// `zk-starks` crate's proof-aggregation module documents for its own
+/// missing curve support, rather than silently approximating it). Instead,
+/// each exported function's multiplier is
+/// the weighted-average opcode cost over its own body *and* the bodies of
+/// every function it statically calls, transitively -- so a thin entry
+/// point that is mostly `call` into a memory-heavy helper is priced by the
+/// helper's actual opcode mix, not by the `Call`-classified wrapper around
+/// it. `call_indirect` targets aren't resolvable statically and stay priced
+/// as a flat `OpcodeClass::Call` at the call site.
+#[derive(Debug, Clone, Default)]
+pub struct GasMeteringProfile {
+    /// function name -> weighted_cost / instruction_count, rolled up over
+    /// the function's whole statically-reachable call graph
+    pub function_multipliers: HashMap<String, f64>,
+    /// Fallback multiplier when a function isn't in the table
+    pub default_multiplier: f64,
+}
+
+impl GasMeteringProfile {
+    /// Build a metering profile for a module: classify every opcode in
+    /// every function body, then roll each exported function's multiplier
+    /// up over its reachable call graph instead of just its own body.
+    pub fn analyze(wasm_bytes: &[u8], cost_table: &HashMap<OpcodeClass, u64>) -> Result<Self, WASMError> {
+        use wasmparser::{Parser, Payload, Operator};
+
+        let mut names: HashMap<u32, String> = HashMap::new();
+        let mut profiles: HashMap<u32, FunctionProfile> = HashMap::new();
+        let mut func_index: u32 = 0;
+        let mut total_weighted = 0u64;
+        let mut total_count = 0u64;
+
+        for payload in Parser::new(0).parse_all(wasm_bytes) {
+            let payload = payload.map_err(|e| WASMError::Validation(format!("Failed to parse module for gas analysis: {}", e)))?;
+            match payload {
+                Payload::ExportSection(reader) => {
+                    for export in reader {
+                        let export = export.map_err(|e| WASMError::Validation(e.to_string()))?;
+                        if let wasmparser::ExternalKind::Func = export.kind {
+                            names.insert(export.index, export.name.to_string());
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let mut weighted = 0u64;
+                    let mut count = 0u64;
+                    let mut callees = std::collections::HashSet::new();
+                    let mut reader = body.get_operators_reader()
+                        .map_err(|e| WASMError::Validation(e.to_string()))?;
+
+                    while !reader.eof() {
+                        let op = reader.read().map_err(|e| WASMError::Validation(e.to_string()))?;
+                        if let Operator::Call { function_index: callee } = op {
+                            callees.insert(callee);
+                        }
+                        let class = classify_opcode(&op);
+                        weighted += *cost_table.get(&class).unwrap_or(&1);
+                        count += 1;
+                    }
+
+                    if count > 0 {
+                        total_weighted += weighted;
+                        total_count += count;
+                    }
+                    profiles.insert(func_index, FunctionProfile { own_weighted: weighted, own_count: count, callees });
+
+                    func_index += 1;
+                }
+                _ => {}
+            }
+        }
+
+        let mut function_multipliers = HashMap::new();
+        for (&index, name) in &names {
+            let (weighted, count) = reachable_cost(index, &profiles);
+            if count > 0 {
+                function_multipliers.insert(name.clone(), weighted as f64 / count as f64);
+            }
         }
+
+        let default_multiplier = if total_count > 0 {
+            total_weighted as f64 / total_count as f64
+        } else {
+            1.0
+        };
+
+        Ok(Self { function_multipliers, default_multiplier })
+    }
+
+    /// Weighted multiplier to apply to raw fuel consumed while running `function_name`.
+    pub fn multiplier_for(&self, function_name: &str) -> f64 {
+        self.function_multipliers.get(function_name).copied().unwrap_or(self.default_multiplier)
+    }
+}
+
+/// Sums `own_weighted`/`own_count` over `root` and every function index
+/// reachable from it through `callees`, each visited once (guards against
+/// cycles from direct recursion or mutual recursion).
+fn reachable_cost(root: u32, profiles: &HashMap<u32, FunctionProfile>) -> (u64, u64) {
+    let mut visited = std::collections::HashSet::new();
+    let mut stack = vec![root];
+    let mut weighted = 0u64;
+    let mut count = 0u64;
+
+    while let Some(index) = stack.pop() {
+        if !visited.insert(index) {
+            continue;
+        }
+        if let Some(profile) = profiles.get(&index) {
+            weighted += profile.own_weighted;
+            count += profile.own_count;
+            stack.extend(profile.callees.iter().copied());
+        }
+    }
+
+    (weighted, count)
+}
+
+/// The only `env` imports a guest module may require; kept in lockstep with
+/// the host functions wired up in `register_host_functions`.
+const ALLOWED_ENV_IMPORTS: &[&str] = &[
+    "storage_read",
+    "storage_write",
+    "get_caller",
+    "get_block_number",
+    "get_block_timestamp",
+];
+
+/// Reject floating-point, SIMD, and threads-proposal opcodes so that
+/// execution is bit-for-bit reproducible across consensus-validating nodes.
+/// Matches on the opcode's debug name rather than enumerating every variant,
+/// since the SIMD/threads proposals alone add hundreds of operators.
+fn disallowed_opcode_reason(op: &wasmparser::Operator) -> Option<&'static str> {
+    let name = format!("{:?}", op);
+    if name.starts_with("F32") || name.starts_with("F64") {
+        Some("floating-point opcodes are non-deterministic across hardware")
+    } else if name.starts_with("V128") || name.contains("Simd") {
+        Some("the SIMD proposal is disabled for consensus-safe execution")
+    } else if name.starts_with("Atomic") || name.contains("Atomic") {
+        Some("the threads/atomics proposal is disabled for consensus-safe execution")
+    } else {
+        None
+    }
+}
+
+fn classify_opcode(op: &wasmparser::Operator) -> OpcodeClass {
+    use wasmparser::Operator::*;
+    match op {
+        I32Load { .. } | I64Load { .. } | F32Load { .. } | F64Load { .. }
+        | I32Load8S { .. } | I32Load8U { .. } | I32Load16S { .. } | I32Load16U { .. }
+        | I64Load8S { .. } | I64Load8U { .. } | I64Load16S { .. } | I64Load16U { .. }
+        | I64Load32S { .. } | I64Load32U { .. }
+        | I32Store { .. } | I64Store { .. } | F32Store { .. } | F64Store { .. }
+        | I32Store8 { .. } | I32Store16 { .. } | I64Store8 { .. } | I64Store16 { .. } | I64Store32 { .. }
+        | MemoryGrow { .. } | MemorySize { .. } | MemoryCopy { .. } | MemoryFill { .. } => OpcodeClass::Memory,
+        Call { .. } | CallIndirect { .. } => OpcodeClass::Call,
+        I32Add | I32Sub | I32Mul | I32DivS | I32DivU | I32RemS | I32RemU
+        | I64Add | I64Sub | I64Mul | I64DivS | I64DivU | I64RemS | I64RemU
+        | F32Add | F32Sub | F32Mul | F32Div | F64Add | F64Sub | F64Mul | F64Div
+        | I32And | I32Or | I32Xor | I64And | I64Or | I64Xor
+        | I32Eq | I32Ne | I32LtS | I32LtU | I32GtS | I32GtU | I32LeS | I32LeU | I32GeS | I32GeU => OpcodeClass::Arithmetic,
+        _ => OpcodeClass::Other,
     }
 }
 
@@ -174,80 +403,341 @@ pub struct WASMVM {
     
     /// Contract registry
     contracts: Arc<RwLock<HashMap<Uuid, ContractMetadata>>>,
-    
+
     /// Contract instances
     instances: Arc<RwLock<HashMap<Uuid, ContractInstance>>>,
+
+    /// Validated modules keyed by `sha256(wasm_bytes)`, so instantiating the
+    /// same code twice (e.g. two counterfactual deployments of one template)
+    /// clones an already-compiled `Module` instead of re-parsing the bytes.
+    code_cache: Arc<RwLock<HashMap<[u8; 32], Module>>>,
 }
 
 /// Contract instance
 struct ContractInstance {
     /// WASM module
     module: Module,
-    
+
     /// Instance store
-    store: Store<()>,
-    
+    store: Store<ContractEnv>,
+
     /// Contract metadata
     metadata: ContractMetadata,
+
+    /// Static gas metering profile computed at deploy time
+    gas_profile: Arc<GasMeteringProfile>,
+
+    /// Persistent key/value storage for this contract, shared across calls
+    storage: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+
+    /// Hash of the code this instance was instantiated from
+    code_hash: [u8; 32],
+
+    /// Native balance credited at instantiation time (the `endowment`) and
+    /// adjusted by whatever transfer logic a contract implements
+    balance: Arc<RwLock<u64>>,
+
+    /// Reversible log of `storage` mutations for the call currently in
+    /// flight, so a trapped execution can be rolled back instead of leaving
+    /// partially-applied writes
+    journal: Arc<RwLock<StorageJournal>>,
+}
+
+/// A single reversible mutation: the key that was written and the value it
+/// held immediately beforehand (`None` if the key was previously absent).
+struct JournalEntry {
+    key: String,
+    prior: Option<Vec<u8>>,
+}
+
+/// Reversible log of `storage_write` mutations, organized into nested
+/// frames. `execute_contract` opens the outermost frame before running a
+/// function and commits it when `ExecutionResult.success` is true, or
+/// replays it in reverse otherwise. Frames nest so that a cross-contract
+/// call (a function that invokes another deployed contract) can roll back
+/// just its own inner frame on failure while the outer frame continues;
+/// nesting is capped by `WASMConfig.max_stack_size` to bound recursive
+/// cross-contract calls.
+struct StorageJournal {
+    frames: Vec<Vec<JournalEntry>>,
+    max_depth: usize,
+}
+
+impl StorageJournal {
+    fn new(max_depth: usize) -> Self {
+        Self { frames: Vec::new(), max_depth }
+    }
+
+    /// Open a new nested frame. Returns the resulting depth (1 = outermost).
+    fn checkpoint(&mut self) -> Result<usize, WASMError> {
+        if self.frames.len() >= self.max_depth {
+            return Err(WASMError::Execution(format!(
+                "storage journal exceeded max nesting depth of {}", self.max_depth
+            )));
+        }
+        self.frames.push(Vec::new());
+        Ok(self.frames.len())
+    }
+
+    /// Record a mutation against the innermost open frame. A no-op if no
+    /// frame is open (defensive; every `storage_write` happens inside a
+    /// call that has already checkpointed).
+    fn record(&mut self, key: String, prior: Option<Vec<u8>>) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.push(JournalEntry { key, prior });
+        }
+    }
+
+    /// Accept the innermost frame's mutations. If an outer frame is still
+    /// open, its entries are folded into it so that a later revert of the
+    /// outer frame still undoes what this frame already committed.
+    fn commit(&mut self) {
+        if let Some(frame) = self.frames.pop() {
+            if let Some(parent) = self.frames.last_mut() {
+                parent.extend(frame);
+            }
+        }
+    }
+
+    /// Discard the innermost frame, restoring every key it touched to the
+    /// value (or absence) it held before the frame was opened.
+    fn revert(&mut self, storage: &mut HashMap<String, Vec<u8>>) {
+        if let Some(frame) = self.frames.pop() {
+            for entry in frame.into_iter().rev() {
+                match entry.prior {
+                    Some(value) => { storage.insert(entry.key, value); }
+                    None => { storage.remove(&entry.key); }
+                }
+            }
+        }
+    }
+}
+
+/// Combined store data: WASI context plus the host-function environment
+/// exposed to guest modules (storage access and caller/block context).
+struct ContractEnv {
+    wasi: wasmtime_wasi::WasiCtx,
+    host: HostEnv,
+}
+
+/// Per-call context made available to `storage_read`/`storage_write`/
+/// `get_caller`/`get_block_number`/`get_block_timestamp` host imports.
+struct HostEnv {
+    storage: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+    caller: String,
+    block_number: u64,
+    block_timestamp: u64,
+    /// Every `storage_write` appends here so `execute_contract` can report
+    /// `ExecutionResult.state_changes` without re-diffing storage.
+    state_changes: Vec<StateChange>,
+    /// Shared with `ContractInstance.journal` so `storage_write` can record
+    /// a reversible entry against the currently open checkpoint frame.
+    journal: Arc<RwLock<StorageJournal>>,
+}
+
+/// Host-side failures surfaced to the guest as a trap and to the caller as a
+/// precise, non-panicking `WASMError::Execution`.
+#[derive(Debug, thiserror::Error)]
+pub enum HostTrap {
+    #[error("storage read failed")]
+    StorageReadError,
+    #[error("storage update failed")]
+    StorageUpdateError,
+    #[error("guest memory access violation")]
+    MemoryAccessViolation,
+    #[error("invalid gas state")]
+    InvalidGasState,
+    #[error("allocation failed")]
+    AllocationFailed,
+}
+
+impl From<HostTrap> for WASMError {
+    fn from(trap: HostTrap) -> Self {
+        WASMError::Execution(trap.to_string())
+    }
 }
 
 impl WASMVM {
     /// Create a new WASM VM
     pub fn new(config: WASMConfig) -> Result<Self, WASMError> {
-        let engine = Engine::new()?;
-        
+        let mut engine_config = wasmtime::Config::new();
+        engine_config.consume_fuel(true);
+        let engine = Engine::new(&engine_config)?;
+
         Ok(Self {
             engine,
             config,
             contracts: Arc::new(RwLock::new(HashMap::new())),
             instances: Arc::new(RwLock::new(HashMap::new())),
+            code_cache: Arc::new(RwLock::new(HashMap::new())),
         })
     }
-    
-    /// Deploy a smart contract
-    pub async fn deploy_contract(
+
+    /// Validate and compile `wasm_bytes`, caching the result by its
+    /// `sha256` code hash so later `instantiate` calls for the same code
+    /// (e.g. several counterfactual deployments of one template) clone an
+    /// already-compiled `Module` rather than re-parsing identical bytes.
+    pub fn upload_code(&self, wasm_bytes: &[u8]) -> Result<[u8; 32], WASMError> {
+        let code_hash: [u8; 32] = Sha256::digest(wasm_bytes).into();
+
+        {
+            let cache = self.code_cache.read()
+                .map_err(|_| WASMError::Internal("Failed to acquire read lock".to_string()))?;
+            if cache.contains_key(&code_hash) {
+                return Ok(code_hash);
+            }
+        }
+
+        self.validate_module(wasm_bytes)?;
+        let module = Module::new(&self.engine, wasm_bytes)?;
+
+        let mut cache = self.code_cache.write()
+            .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?;
+        cache.insert(code_hash, module);
+
+        Ok(code_hash)
+    }
+
+    /// Compute the deterministic address a contract instantiated from
+    /// `code_hash` by `caller` with `salt` would be assigned, without
+    /// actually instantiating it. This is what makes counterfactual
+    /// deployment possible: a caller can fund an address before any code
+    /// has run there.
+    pub fn contract_address(code_hash: &[u8; 32], caller: &str, salt: &[u8]) -> Uuid {
+        let mut hasher = Sha256::new();
+        hasher.update(code_hash);
+        hasher.update(caller.as_bytes());
+        hasher.update(salt);
+        let digest = hasher.finalize();
+        Uuid::from_slice(&digest[..16]).expect("sha256 digest is at least 16 bytes")
+    }
+
+    /// Upload `wasm_bytes` (sharing the compiled `Module` with any other
+    /// instance of the same code), run `constructor_name` once to populate
+    /// initial storage, credit `endowment` to the new contract's balance,
+    /// and register it under the address deterministically derived from
+    /// `(code_hash, caller, salt)` — the same inputs always yield the same
+    /// address, so deployments are counterfactual-deployment friendly.
+    ///
+    /// `constructor_name` must be declared in `metadata.functions` like any
+    /// other contract function (typically with `FunctionVisibility::Private`
+    /// so it cannot be called again through `execute_contract`).
+    pub async fn instantiate(
         &self,
         wasm_bytes: &[u8],
-        metadata: ContractMetadata,
+        mut metadata: ContractMetadata,
+        constructor_name: &str,
+        init_params: &[u8],
+        endowment: u64,
+        salt: &[u8],
+        context: &ExecutionContext,
     ) -> Result<Uuid, WASMError> {
-        // Validate WASM module
-        let module = Module::new(&self.engine, wasm_bytes)?;
-        
-        // Create WASI context
+        let code_hash = self.upload_code(wasm_bytes)?;
+        self.validate_exports(wasm_bytes, &metadata)?;
+        let contract_id = Self::contract_address(&code_hash, &context.caller, salt);
+
+        {
+            let instances = self.instances.read()
+                .map_err(|_| WASMError::Internal("Failed to acquire read lock".to_string()))?;
+            if instances.contains_key(&contract_id) {
+                return Err(WASMError::Validation(format!(
+                    "contract already instantiated at deterministic address {}", contract_id
+                )));
+            }
+        }
+
+        let module = {
+            let cache = self.code_cache.read()
+                .map_err(|_| WASMError::Internal("Failed to acquire read lock".to_string()))?;
+            cache.get(&code_hash).cloned()
+                .ok_or_else(|| WASMError::Internal("code hash missing from cache right after upload".to_string()))?
+        };
+
         let wasi = WasiCtxBuilder::new()
             .inherit_stdio()
             .inherit_args()?
             .build();
-        
-        // Create store
-        let mut store = Store::new(&self.engine, wasi);
-        
-        // Validate module
-        self.validate_module(&module)?;
-        
-        // Create contract instance
+
+        let storage = Arc::new(RwLock::new(HashMap::new()));
+        let journal = Arc::new(RwLock::new(StorageJournal::new(self.config.max_stack_size)));
+
+        let store = Store::new(&self.engine, ContractEnv {
+            wasi,
+            host: HostEnv {
+                storage: storage.clone(),
+                caller: String::new(),
+                block_number: 0,
+                block_timestamp: 0,
+                state_changes: Vec::new(),
+                journal: journal.clone(),
+            },
+        });
+
+        let gas_profile = GasMeteringProfile::analyze(wasm_bytes, &self.config.gas_cost_table)?;
+
+        metadata.contract_id = contract_id;
+
         let instance = ContractInstance {
             module,
             store,
             metadata: metadata.clone(),
+            gas_profile: Arc::new(gas_profile),
+            storage,
+            code_hash,
+            balance: Arc::new(RwLock::new(endowment)),
+            journal,
         };
-        
-        // Register contract
+
+        // Run the constructor exactly once, ahead of registration, so it
+        // cannot be reached again through `execute_contract`. A failed
+        // constructor must leave no partial storage writes behind.
+        let mut constructor_context = context.clone();
+        constructor_context.contract_id = contract_id;
+        instance.journal.write()
+            .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?
+            .checkpoint()?;
+        match self.execute_function(&instance, constructor_name, init_params, &constructor_context).await {
+            Ok(_) => {
+                instance.journal.write()
+                    .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?
+                    .commit();
+            }
+            Err(e) => {
+                let mut storage = instance.storage.write()
+                    .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?;
+                instance.journal.write()
+                    .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?
+                    .revert(&mut storage);
+                return Err(e);
+            }
+        };
+
         {
             let mut contracts = self.contracts.write()
                 .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?;
-            contracts.insert(metadata.contract_id, metadata);
+            contracts.insert(contract_id, metadata);
         }
-        
+
         {
             let mut instances = self.instances.write()
                 .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?;
-            instances.insert(metadata.contract_id, instance);
+            instances.insert(contract_id, instance);
         }
-        
-        Ok(metadata.contract_id)
+
+        Ok(contract_id)
     }
-    
+
+    /// Native balance credited to a contract at `instantiate` time.
+    pub fn contract_balance(&self, contract_id: &Uuid) -> Result<u64, WASMError> {
+        let instances = self.instances.read()
+            .map_err(|_| WASMError::Internal("Failed to acquire read lock".to_string()))?;
+        let instance = instances.get(contract_id)
+            .ok_or_else(|| WASMError::ContractNotFound(format!("Contract {} not found", contract_id)))?;
+        let balance = instance.balance.read()
+            .map_err(|_| WASMError::Internal("Failed to acquire read lock".to_string()))?;
+        Ok(*balance)
+    }
+
     /// Execute a smart contract function
     pub async fn execute_contract(
         &self,
@@ -277,93 +767,423 @@ impl WASMVM {
         if function.visibility == FunctionVisibility::Private {
             return Err(WASMError::AccessDenied("Function is private".to_string()));
         }
-        
-        // Execute function
-        let result = self.execute_function(&instance, function_name, parameters, context).await?;
-        
+
+        // Checkpoint storage so a trap leaves no partially-applied writes:
+        // the journal frame opened here is committed on success or replayed
+        // in reverse on any failure path below.
+        instance.journal.write()
+            .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?
+            .checkpoint()?;
+
+        // Execute function, metering gas via wasmtime fuel
+        let (result, gas_used, state_changes) = match self.execute_function(&instance, function_name, parameters, context).await {
+            Ok((value, gas_used, state_changes)) => (Ok(value), gas_used, state_changes),
+            Err(WASMError::OutOfGas(_)) => (Err(WASMError::Execution("out of gas".to_string())), context.gas_limit, Vec::new()),
+            Err(e) => (Err(e), 0, Vec::new()),
+        };
+
+        if result.is_ok() {
+            instance.journal.write()
+                .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?
+                .commit();
+        } else {
+            let mut storage = instance.storage.write()
+                .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?;
+            instance.journal.write()
+                .map_err(|_| WASMError::Internal("Failed to acquire write lock".to_string()))?
+                .revert(&mut storage);
+        }
+
         let execution_time = start_time.elapsed();
-        
+
         Ok(ExecutionResult {
             success: result.is_ok(),
-            return_value: result.ok(),
-            gas_used: 0, // TODO: Implement gas metering
+            return_value: result.as_ref().ok().cloned(),
+            gas_used,
             execution_time_ms: execution_time.as_millis() as u64,
             error_message: result.err().map(|e| e.to_string()),
-            state_changes: Vec::new(), // TODO: Track state changes
+            state_changes,
         })
     }
-    
-    /// Execute a function in the WASM module
+
+    /// Execute a function in the WASM module, returning the raw result bytes,
+    /// the weighted gas consumed, and the state changes recorded through the
+    /// `storage_write` host import. The store's wasmtime fuel is
+    /// `context.gas_limit` scaled down by the function's weighted
+    /// multiplier, so memory/call-heavy code exhausts its (smaller) raw
+    /// budget sooner and trips `WASMError::OutOfGas` at roughly the
+    /// intended weighted cost instead of only being billed for it after
+    /// the call already ran unbounded.
     async fn execute_function(
         &self,
         instance: &ContractInstance,
         function_name: &str,
         parameters: &[u8],
-        _context: &ExecutionContext,
-    ) -> Result<Vec<u8>, WASMError> {
+        context: &ExecutionContext,
+    ) -> Result<(Vec<u8>, u64, Vec<StateChange>), WASMError> {
         // Create linker
         let mut linker = Linker::new(&self.engine);
-        
+
         // Add WASI functions
-        wasmtime_wasi::add_to_linker(&mut linker, |s| s)?;
-        
+        wasmtime_wasi::add_to_linker(&mut linker, |s: &mut ContractEnv| &mut s.wasi)?;
+
+        // Add SDUPI host functions: storage access and caller/block context
+        register_host_functions(&mut linker)?;
+
+        // wasmtime only ever charges fuel 1 unit/instruction, so to actually
+        // bound the *weighted* cost live (rather than just compute it for
+        // reporting after the call already ran) the raw fuel budget itself
+        // is shrunk by this function's multiplier: a memory/call-heavy
+        // function burns its (smaller) raw budget faster, tripping
+        // `OutOfGas` at roughly the same weighted cost a cheaper function
+        // would hit at `gas_limit` raw instructions.
+        let multiplier = instance.gas_profile.multiplier_for(function_name).max(1.0);
+        let raw_fuel_budget = ((context.gas_limit as f64) / multiplier).floor().max(1.0) as u64;
+
+        let mut store = instance.store.clone();
+        store.set_fuel(raw_fuel_budget)
+            .map_err(|e| WASMError::Internal(format!("Failed to set fuel: {}", e)))?;
+
+        // Refresh the per-call host environment
+        {
+            let env = store.data_mut();
+            env.host.caller = context.caller.clone();
+            env.host.block_number = context.block_number;
+            env.host.block_timestamp = context.block_timestamp;
+            env.host.state_changes.clear();
+        }
+
         // Instantiate module
-        let instance = linker.instantiate(&mut instance.store.clone(), &instance.module)?;
-        
+        let wasm_instance = linker.instantiate(&mut store, &instance.module)?;
+
         // Get function
-        let function = instance.get_func(&mut instance.store, function_name)
-            .map_err(|_| WASMError::FunctionNotFound(format!("Function {} not found in WASM module", function_name)))?;
-        
+        let function = wasm_instance.get_func(&mut store, function_name)
+            .ok_or_else(|| WASMError::FunctionNotFound(format!("Function {} not found in WASM module", function_name)))?;
+
+        // Look up the declared signature so parameters/results can be encoded
+        // according to the ABI instead of being dropped on the floor.
+        let signature = instance.metadata.functions.iter()
+            .find(|f| f.name == function_name)
+            .ok_or_else(|| WASMError::FunctionNotFound(format!("Function {} has no declared signature", function_name)))?;
+
+        let memory = wasm_instance.get_memory(&mut store, "memory");
+
         // Prepare parameters
-        let params = self.prepare_function_parameters(parameters)?;
-        
-        // Execute function
-        let results = function.call(&mut instance.store, &params, &mut [])?;
-        
+        let params = self.prepare_function_parameters(&mut store, &wasm_instance, memory.as_ref(), signature, parameters)?;
+
+        // Execute function, tracking fuel before/after to derive raw gas used
+        let remaining_before = store.get_fuel().unwrap_or(context.gas_limit);
+        let mut raw_results = vec![wasmtime::Val::I32(0); function.ty(&store).results().len()];
+        let call_result = function.call(&mut store, &params, &mut raw_results);
+        let remaining_after = store.get_fuel().unwrap_or(0);
+        let raw_fuel_used = remaining_before.saturating_sub(remaining_after);
+        let gas_used = ((raw_fuel_used as f64) * multiplier).ceil() as u64;
+
+        if let Err(trap) = call_result {
+            if remaining_after == 0 {
+                return Err(WASMError::OutOfGas(context.gas_limit));
+            }
+            return Err(WASMError::Execution(trap.to_string()));
+        }
+
         // Convert results
-        let return_value = self.convert_function_results(&results)?;
-        
-        Ok(return_value)
+        let return_value = self.convert_function_results(
+            &mut store,
+            memory.as_ref(),
+            signature.return_type.as_deref(),
+            &raw_results,
+        )?;
+
+        let state_changes = std::mem::take(&mut store.data_mut().host.state_changes);
+
+        Ok((return_value, gas_used, state_changes))
     }
-    
-    /// Prepare function parameters
-    fn prepare_function_parameters(&self, parameters: &[u8]) -> Result<Vec<wasmtime::Val>, WASMError> {
-        // TODO: Implement parameter parsing based on function signature
-        // For now, return empty parameters
-        Ok(Vec::new())
+
+    /// Decode the raw call payload into a `Vec<wasmtime::Val>` following the
+    /// function's declared parameter types.
+    ///
+    /// The payload uses a canonical head/tail ABI: one 8-byte head word per
+    /// declared parameter, followed by a tail section holding the bytes for
+    /// any dynamically-sized arguments. For `i32`/`u32`/`bool` the head word
+    /// *is* the value (low 4 bytes, little-endian). For `i64`/`u64` the full
+    /// 8-byte head word is the value. For everything else (`string`, `bytes`,
+    /// or any other declared type) the head word is a `u32` byte offset into
+    /// the tail where a 4-byte little-endian length prefix is followed by the
+    /// argument's raw bytes; those bytes are copied into the guest's linear
+    /// memory via its exported `alloc` allocator and passed down as a
+    /// `(ptr, len)` pair of i32s.
+    fn prepare_function_parameters(
+        &self,
+        store: &mut Store<ContractEnv>,
+        wasm_instance: &Instance,
+        memory: Option<&wasmtime::Memory>,
+        signature: &ContractFunction,
+        parameters: &[u8],
+    ) -> Result<Vec<wasmtime::Val>, WASMError> {
+        const HEAD_WORD: usize = 8;
+
+        let head_len = signature.parameters.len() * HEAD_WORD;
+        if parameters.len() < head_len {
+            return Err(WASMError::Validation(format!(
+                "parameter payload of {} bytes is too short for {} declared parameter(s)",
+                parameters.len(),
+                signature.parameters.len()
+            )));
+        }
+        let tail = &parameters[head_len..];
+
+        let mut vals = Vec::with_capacity(signature.parameters.len() * 2);
+        for (index, param) in signature.parameters.iter().enumerate() {
+            let word: [u8; HEAD_WORD] = parameters[index * HEAD_WORD..(index + 1) * HEAD_WORD]
+                .try_into()
+                .map_err(|_| WASMError::Validation("malformed parameter head".to_string()))?;
+
+            match param.parameter_type.as_str() {
+                "bool" | "i32" | "u32" => {
+                    let value = u32::from_le_bytes(word[0..4].try_into().unwrap());
+                    vals.push(wasmtime::Val::I32(value as i32));
+                }
+                "i64" | "u64" => {
+                    vals.push(wasmtime::Val::I64(i64::from_le_bytes(word)));
+                }
+                _ => {
+                    let offset = u32::from_le_bytes(word[0..4].try_into().unwrap()) as usize;
+                    let length_bytes = tail.get(offset..offset + 4).ok_or_else(|| {
+                        WASMError::Validation(format!(
+                            "parameter `{}` tail offset {} is out of bounds",
+                            param.name, offset
+                        ))
+                    })?;
+                    let length = u32::from_le_bytes(length_bytes.try_into().unwrap()) as usize;
+                    let data = tail.get(offset + 4..offset + 4 + length).ok_or_else(|| {
+                        WASMError::Validation(format!(
+                            "parameter `{}` declares {} bytes past the end of the payload",
+                            param.name, length
+                        ))
+                    })?;
+
+                    let memory = memory.ok_or_else(|| {
+                        WASMError::Validation("contract module does not export linear memory".to_string())
+                    })?;
+                    let alloc = wasm_instance.get_typed_func::<i32, i32>(&mut *store, "alloc")
+                        .map_err(|_| WASMError::Validation(
+                            "contract module does not export an `alloc` allocator required for dynamic parameters".to_string()
+                        ))?;
+                    let guest_ptr = alloc.call(&mut *store, length as i32)?;
+                    write_store_bytes(store, memory, guest_ptr, data)?;
+
+                    vals.push(wasmtime::Val::I32(guest_ptr));
+                    vals.push(wasmtime::Val::I32(length as i32));
+                }
+            }
+        }
+
+        Ok(vals)
     }
-    
-    /// Convert function results
-    fn convert_function_results(&self, results: &[wasmtime::Val]) -> Result<Vec<u8>, WASMError> {
-        // TODO: Implement result conversion based on return type
-        // For now, return empty result
-        Ok(Vec::new())
+
+    /// Encode the guest's raw `wasmtime::Val` results into the
+    /// `ExecutionResult.return_value` byte buffer according to the
+    /// function's declared `return_type`, mirroring the head/tail
+    /// convention used by `prepare_function_parameters`. Integer types are
+    /// read directly off the single result value; `string`/`bytes` (and any
+    /// other dynamic type) are read as a `(ptr, len)` pair out of guest
+    /// memory.
+    fn convert_function_results(
+        &self,
+        store: &mut Store<ContractEnv>,
+        memory: Option<&wasmtime::Memory>,
+        return_type: Option<&str>,
+        results: &[wasmtime::Val],
+    ) -> Result<Vec<u8>, WASMError> {
+        let Some(return_type) = return_type else {
+            return Ok(Vec::new());
+        };
+
+        match return_type {
+            "bool" | "i32" | "u32" => {
+                let value = results.first()
+                    .and_then(|v| v.i32())
+                    .ok_or_else(|| WASMError::Validation("function did not return an i32 result".to_string()))?;
+                Ok((value as u32).to_le_bytes().to_vec())
+            }
+            "i64" | "u64" => {
+                let value = results.first()
+                    .and_then(|v| v.i64())
+                    .ok_or_else(|| WASMError::Validation("function did not return an i64 result".to_string()))?;
+                Ok(value.to_le_bytes().to_vec())
+            }
+            _ => {
+                let ptr = results.first()
+                    .and_then(|v| v.i32())
+                    .ok_or_else(|| WASMError::Validation("dynamic return type expects a (ptr, len) pair".to_string()))?;
+                let len = results.get(1)
+                    .and_then(|v| v.i32())
+                    .ok_or_else(|| WASMError::Validation("dynamic return type expects a (ptr, len) pair".to_string()))?;
+                let memory = memory.ok_or_else(|| {
+                    WASMError::Validation("contract module does not export linear memory".to_string())
+                })?;
+                read_store_bytes(store, memory, ptr, len)
+            }
+        }
     }
     
-    /// Validate WASM module
-    fn validate_module(&self, module: &Module) -> Result<(), WASMError> {
-        // Check memory size
-        if let Some(memory) = module.memory_section() {
-            for memory_type in memory {
-                if memory_type.initial > self.config.max_memory_size as u32 {
-                    return Err(WASMError::Validation(
-                        format!("Memory size {} exceeds limit {}", memory_type.initial, self.config.max_memory_size)
-                    ));
+    /// Statically validate a module's bytecode with `wasmparser` before it is
+    /// ever compiled into a cached `Module`: only the host imports this VM
+    /// actually exposes may be required, floating-point and the SIMD/threads
+    /// proposals are rejected so that execution stays bit-for-bit
+    /// reproducible across consensus-validating nodes, the declared memory
+    /// must carry a maximum within `max_memory_size`, and the function/
+    /// global/table counts are capped.
+    fn validate_module(&self, wasm_bytes: &[u8]) -> Result<(), WASMError> {
+        use wasmparser::{Parser, Payload, TypeRef};
+
+        let mut function_count = 0usize;
+        let mut global_count = 0usize;
+        let mut table_entry_count = 0usize;
+
+        for payload in Parser::new(0).parse_all(wasm_bytes) {
+            let payload = payload.map_err(|e| WASMError::Validation(format!("Failed to parse module: {}", e)))?;
+            match payload {
+                Payload::ImportSection(reader) => {
+                    for import in reader {
+                        let import = import.map_err(|e| WASMError::Validation(e.to_string()))?;
+                        match import.ty {
+                            TypeRef::Func(_) => {
+                                function_count += 1;
+                                if import.module == "env" {
+                                    if !ALLOWED_ENV_IMPORTS.contains(&import.name) {
+                                        return Err(WASMError::Validation(format!(
+                                            "import `env::{}` is not one of the host functions this VM exposes",
+                                            import.name
+                                        )));
+                                    }
+                                } else if import.module != "wasi_snapshot_preview1" {
+                                    return Err(WASMError::Validation(format!(
+                                        "import from disallowed module `{}`; only `env` and WASI imports are permitted",
+                                        import.module
+                                    )));
+                                }
+                            }
+                            _ => {
+                                return Err(WASMError::Validation(
+                                    "only function imports are permitted; memory/table/global imports are rejected".to_string(),
+                                ));
+                            }
+                        }
+                    }
                 }
+                Payload::FunctionSection(reader) => {
+                    for func in reader {
+                        func.map_err(|e| WASMError::Validation(e.to_string()))?;
+                        function_count += 1;
+                    }
+                }
+                Payload::GlobalSection(reader) => {
+                    for global in reader {
+                        global.map_err(|e| WASMError::Validation(e.to_string()))?;
+                        global_count += 1;
+                    }
+                }
+                Payload::TableSection(reader) => {
+                    for table in reader {
+                        let table = table.map_err(|e| WASMError::Validation(e.to_string()))?;
+                        table_entry_count += table.ty.initial as usize;
+                    }
+                }
+                Payload::MemorySection(reader) => {
+                    for memory_type in reader {
+                        let memory_type = memory_type.map_err(|e| WASMError::Validation(e.to_string()))?;
+                        if memory_type.shared {
+                            return Err(WASMError::Validation(
+                                "shared memory is rejected (threads proposal is not deterministic across nodes)".to_string(),
+                            ));
+                        }
+                        if memory_type.memory64 {
+                            return Err(WASMError::Validation(
+                                "64-bit memory is rejected; only the 32-bit memory proposal is supported".to_string(),
+                            ));
+                        }
+                        match memory_type.maximum {
+                            Some(max_pages) => {
+                                let max_bytes = max_pages.saturating_mul(65536);
+                                if max_bytes > self.config.max_memory_size as u64 {
+                                    return Err(WASMError::Validation(format!(
+                                        "declared memory maximum of {} pages ({} bytes) exceeds the limit of {} bytes",
+                                        max_pages, max_bytes, self.config.max_memory_size
+                                    )));
+                                }
+                            }
+                            None => {
+                                return Err(WASMError::Validation(
+                                    "module memory must declare a maximum; unbounded memory cannot be gas-metered safely".to_string(),
+                                ));
+                            }
+                        }
+                    }
+                }
+                Payload::CodeSectionEntry(body) => {
+                    let mut reader = body.get_operators_reader()
+                        .map_err(|e| WASMError::Validation(e.to_string()))?;
+                    while !reader.eof() {
+                        let op = reader.read().map_err(|e| WASMError::Validation(e.to_string()))?;
+                        if let Some(reason) = disallowed_opcode_reason(&op) {
+                            return Err(WASMError::Validation(format!(
+                                "opcode `{:?}` is rejected: {}", op, reason
+                            )));
+                        }
+                    }
+                }
+                _ => {}
             }
         }
-        
-        // Check stack size
-        if let Some(code) = module.code_section() {
-            for function_body in code {
-                if function_body.code().len() > self.config.max_stack_size {
-                    return Err(WASMError::Validation(
-                        format!("Code size {} exceeds limit {}", function_body.code().len(), self.config.max_stack_size)
-                    ));
+
+        if function_count > self.config.max_functions {
+            return Err(WASMError::Validation(format!(
+                "module declares {} functions, exceeding the limit of {}", function_count, self.config.max_functions
+            )));
+        }
+        if global_count > self.config.max_globals {
+            return Err(WASMError::Validation(format!(
+                "module declares {} globals, exceeding the limit of {}", global_count, self.config.max_globals
+            )));
+        }
+        if table_entry_count > self.config.max_table_entries {
+            return Err(WASMError::Validation(format!(
+                "module declares {} table entries, exceeding the limit of {}", table_entry_count, self.config.max_table_entries
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Require every function named in `metadata.functions` to actually be
+    /// exported by the module, so a deployed contract can never advertise a
+    /// callable function that traps on lookup.
+    fn validate_exports(&self, wasm_bytes: &[u8], metadata: &ContractMetadata) -> Result<(), WASMError> {
+        use wasmparser::{Parser, Payload, ExternalKind};
+
+        let mut exported = std::collections::HashSet::new();
+        for payload in Parser::new(0).parse_all(wasm_bytes) {
+            let payload = payload.map_err(|e| WASMError::Validation(format!("Failed to parse module: {}", e)))?;
+            if let Payload::ExportSection(reader) = payload {
+                for export in reader {
+                    let export = export.map_err(|e| WASMError::Validation(e.to_string()))?;
+                    if let ExternalKind::Func = export.kind {
+                        exported.insert(export.name.to_string());
+                    }
                 }
             }
         }
-        
+
+        for function in &metadata.functions {
+            if !exported.contains(&function.name) {
+                return Err(WASMError::Validation(format!(
+                    "contract metadata declares function `{}` but the module does not export it",
+                    function.name
+                )));
+            }
+        }
+
         Ok(())
     }
     
@@ -401,6 +1221,171 @@ impl WASMVM {
     }
 }
 
+/// Register the SDUPI host-function environment (`storage_read`,
+/// `storage_write`, `get_caller`, `get_block_number`, `get_block_timestamp`)
+/// that deployed contracts import from the `"env"` module. Host failures are
+/// modeled as `HostTrap` and surfaced to the guest as a wasmtime trap, never
+/// a panic.
+fn register_host_functions(linker: &mut Linker<ContractEnv>) -> Result<(), WASMError> {
+    linker.func_wrap(
+        "env",
+        "storage_read",
+        |mut caller: wasmtime::Caller<'_, ContractEnv>, key_ptr: i32, key_len: i32, out_ptr: i32| -> Result<i32, wasmtime::Error> {
+            let memory = guest_memory(&mut caller)?;
+            let key = read_guest_bytes(&mut caller, &memory, key_ptr, key_len)
+                .map_err(|_| wasmtime::Error::from(HostTrap::MemoryAccessViolation))?;
+            let key = String::from_utf8(key).map_err(|_| wasmtime::Error::from(HostTrap::StorageReadError))?;
+
+            let value = {
+                let storage = caller.data().host.storage.read()
+                    .map_err(|_| wasmtime::Error::from(HostTrap::StorageReadError))?;
+                storage.get(&key).cloned()
+            };
+
+            match value {
+                Some(bytes) => {
+                    write_guest_bytes(&mut caller, &memory, out_ptr, &bytes)
+                        .map_err(|_| wasmtime::Error::from(HostTrap::MemoryAccessViolation))?;
+                    Ok(bytes.len() as i32)
+                }
+                None => Ok(-1),
+            }
+        },
+    )?;
+
+    linker.func_wrap(
+        "env",
+        "storage_write",
+        |mut caller: wasmtime::Caller<'_, ContractEnv>, key_ptr: i32, key_len: i32, val_ptr: i32, val_len: i32| -> Result<(), wasmtime::Error> {
+            let memory = guest_memory(&mut caller)?;
+            let key = read_guest_bytes(&mut caller, &memory, key_ptr, key_len)
+                .map_err(|_| wasmtime::Error::from(HostTrap::MemoryAccessViolation))?;
+            let key = String::from_utf8(key).map_err(|_| wasmtime::Error::from(HostTrap::StorageUpdateError))?;
+            let new_value = read_guest_bytes(&mut caller, &memory, val_ptr, val_len)
+                .map_err(|_| wasmtime::Error::from(HostTrap::MemoryAccessViolation))?;
+
+            let old_value = {
+                let mut storage = caller.data().host.storage.write()
+                    .map_err(|_| wasmtime::Error::from(HostTrap::StorageUpdateError))?;
+                storage.insert(key.clone(), new_value.clone())
+            };
+
+            caller.data().host.journal.write()
+                .map_err(|_| wasmtime::Error::from(HostTrap::StorageUpdateError))?
+                .record(key.clone(), old_value.clone());
+
+            caller.data_mut().host.state_changes.push(StateChange {
+                key,
+                old_value,
+                new_value: Some(new_value),
+            });
+
+            Ok(())
+        },
+    )?;
+
+    linker.func_wrap(
+        "env",
+        "get_caller",
+        |mut caller: wasmtime::Caller<'_, ContractEnv>, out_ptr: i32| -> Result<i32, wasmtime::Error> {
+            let memory = guest_memory(&mut caller)?;
+            let bytes = caller.data().host.caller.clone().into_bytes();
+            write_guest_bytes(&mut caller, &memory, out_ptr, &bytes)
+                .map_err(|_| wasmtime::Error::from(HostTrap::MemoryAccessViolation))?;
+            Ok(bytes.len() as i32)
+        },
+    )?;
+
+    linker.func_wrap(
+        "env",
+        "get_block_number",
+        |caller: wasmtime::Caller<'_, ContractEnv>| -> i64 { caller.data().host.block_number as i64 },
+    )?;
+
+    linker.func_wrap(
+        "env",
+        "get_block_timestamp",
+        |caller: wasmtime::Caller<'_, ContractEnv>| -> i64 { caller.data().host.block_timestamp as i64 },
+    )?;
+
+    Ok(())
+}
+
+fn guest_memory(caller: &mut wasmtime::Caller<'_, ContractEnv>) -> Result<wasmtime::Memory, wasmtime::Error> {
+    caller.get_export("memory")
+        .and_then(|e| e.into_memory())
+        .ok_or_else(|| wasmtime::Error::from(HostTrap::MemoryAccessViolation))
+}
+
+fn read_guest_bytes(
+    caller: &mut wasmtime::Caller<'_, ContractEnv>,
+    memory: &wasmtime::Memory,
+    ptr: i32,
+    len: i32,
+) -> Result<Vec<u8>, HostTrap> {
+    if ptr < 0 || len < 0 {
+        return Err(HostTrap::MemoryAccessViolation);
+    }
+    let (start, end) = (ptr as usize, ptr as usize + len as usize);
+    memory.data(caller).get(start..end)
+        .map(|slice| slice.to_vec())
+        .ok_or(HostTrap::MemoryAccessViolation)
+}
+
+fn write_guest_bytes(
+    caller: &mut wasmtime::Caller<'_, ContractEnv>,
+    memory: &wasmtime::Memory,
+    ptr: i32,
+    bytes: &[u8],
+) -> Result<(), HostTrap> {
+    if ptr < 0 {
+        return Err(HostTrap::MemoryAccessViolation);
+    }
+    let start = ptr as usize;
+    let end = start + bytes.len();
+    memory.data_mut(caller).get_mut(start..end)
+        .ok_or(HostTrap::MemoryAccessViolation)?
+        .copy_from_slice(bytes);
+    Ok(())
+}
+
+/// Read `len` bytes of guest linear memory directly off the `Store`, for use
+/// in the ABI codec where there is no `Caller` (the call is driven from
+/// `execute_function`, not from inside a host function).
+fn read_store_bytes(
+    store: &mut Store<ContractEnv>,
+    memory: &wasmtime::Memory,
+    ptr: i32,
+    len: i32,
+) -> Result<Vec<u8>, WASMError> {
+    if ptr < 0 || len < 0 {
+        return Err(WASMError::Validation("negative guest pointer or length".to_string()));
+    }
+    let (start, end) = (ptr as usize, ptr as usize + len as usize);
+    memory.data(&mut *store).get(start..end)
+        .map(|slice| slice.to_vec())
+        .ok_or_else(|| WASMError::Validation("guest memory access out of bounds".to_string()))
+}
+
+/// Write `bytes` into guest linear memory directly off the `Store`, the
+/// `Store`-driven counterpart to `write_guest_bytes`.
+fn write_store_bytes(
+    store: &mut Store<ContractEnv>,
+    memory: &wasmtime::Memory,
+    ptr: i32,
+    bytes: &[u8],
+) -> Result<(), WASMError> {
+    if ptr < 0 {
+        return Err(WASMError::Validation("negative guest pointer".to_string()));
+    }
+    let start = ptr as usize;
+    let end = start + bytes.len();
+    memory.data_mut(&mut *store).get_mut(start..end)
+        .ok_or_else(|| WASMError::Validation("guest memory access out of bounds".to_string()))?
+        .copy_from_slice(bytes);
+    Ok(())
+}
+
 /// WASM VM error types
 #[derive(Debug, thiserror::Error)]
 pub enum WASMError {
@@ -421,7 +1406,10 @@ pub enum WASMError {
     
     #[error("Execution error: {0}")]
     Execution(String),
-    
+
+    #[error("Out of gas: limit {0} exhausted")]
+    OutOfGas(u64),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
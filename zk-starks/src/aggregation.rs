@@ -0,0 +1,234 @@
+//! Batch aggregation of many [`ZKProof`]s sharing the same verification key.
+//!
+//! The request behind this module asks for pairing-based proof recursion;
+//! this crate has no pairing-friendly curve dependency (see
+//! [`range_proof`](crate::range_proof)'s module docs for the same
+//! constraint), so true succinct recursive composition is out of scope.
+//! Instead this implements the part of the ask that *is* honest with the
+//! primitives on hand: a random-linear-combination (RLC) batch check, the
+//! standard technique for collapsing many sigma-protocol verifications into
+//! one. Every inner proof's balance check is a linear Pedersen equality
+//! (`commitment == excess*H`), so `n` such checks fold into a single
+//! combined equality `sum(w_i * commitment_i) == sum(w_i * excess_i) * H`
+//! for random per-proof weights `w_i` bound to the whole batch via
+//! Fiat-Shamir -- if any individual check were false, the combined equality
+//! only holds with negligible probability over the random weights.
+//!
+//! Range sub-proofs are disjunctive (1-of-2 Schnorr OR) rather than plain
+//! linear equalities, so batching them the same way would require
+//! restructuring [`range_proof::BitProof`](crate::range_proof) to expose
+//! its verification residual; this aggregator still verifies each range
+//! sub-proof individually and folds only the balance proofs, which is
+//! where the bulk of a block's proofs (one balance check per transaction)
+//! actually live.
+
+use sha2::{Digest, Sha256};
+
+use k256::elliptic_curve::PrimeField;
+use k256::Scalar;
+
+use crate::value_commitment::{verify_zero, BalanceProof, ValueBlindingFactor, ValueCommitment};
+use crate::{decode_proof_bundle, range_proof, ProofBundle, ZKError, ZKProof};
+
+/// A single combined balance check folded from many [`BalanceProof`]s via
+/// random linear combination.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct BalanceBatch {
+    combined_commitment: ValueCommitment,
+    combined_excess: ValueBlindingFactor,
+}
+
+impl BalanceBatch {
+    fn verify(&self) -> Result<bool, ZKError> {
+        verify_zero(&self.combined_commitment, &self.combined_excess)
+    }
+}
+
+/// The result of folding many [`ZKProof`]s that share a verification key
+/// into one aggregate, ready for a single [`ZKVerifier::verify_aggregated`](crate::ZKVerifier::verify_aggregated) call.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AggregatedProof {
+    pub verification_key: Vec<u8>,
+    /// Number of proofs folded into this aggregate.
+    pub count: usize,
+    /// Range sub-proofs from every inner proof, still checked individually.
+    range_proofs: Vec<range_proof::RangeProof>,
+    /// The inner balance proofs folded into one combined check, if any of
+    /// the aggregated proofs carried one.
+    balance_batch: Option<BalanceBatch>,
+}
+
+/// Folds many [`ZKProof`]s sharing a circuit/verification key into one
+/// [`AggregatedProof`].
+pub struct ProofAggregator;
+
+impl ProofAggregator {
+    /// Aggregates `proofs`, which must all share the same verification key.
+    pub fn aggregate(proofs: &[ZKProof]) -> Result<AggregatedProof, ZKError> {
+        if proofs.is_empty() {
+            return Err(ZKError::ProofGeneration("cannot aggregate an empty proof set".to_string()));
+        }
+
+        let verification_key = proofs[0].verification_key.clone();
+        if proofs.iter().any(|p| p.verification_key != verification_key) {
+            return Err(ZKError::ProofGeneration(
+                "all proofs must share the same verification key to aggregate".to_string(),
+            ));
+        }
+
+        let bundles = proofs.iter().map(decode_proof_bundle).collect::<Result<Vec<ProofBundle>, _>>()?;
+
+        let range_proofs = bundles.iter().flat_map(|b| b.range_proofs.iter().cloned()).collect();
+        let balance_batch = fold_balance_proofs(bundles.iter().filter_map(|b| b.balance_proof.as_ref()))?;
+
+        Ok(AggregatedProof { verification_key, count: proofs.len(), range_proofs, balance_batch })
+    }
+}
+
+impl crate::ZKVerifier {
+    /// Verifies an [`AggregatedProof`] with one combined balance check
+    /// instead of `count` independent ones.
+    pub fn verify_aggregated(&self, aggregated: &AggregatedProof) -> Result<bool, ZKError> {
+        if aggregated.verification_key != self.verification_key {
+            return Err(ZKError::Verification("Verification key mismatch".to_string()));
+        }
+
+        for range_proof in &aggregated.range_proofs {
+            if !range_proof.verify()? {
+                return Ok(false);
+            }
+        }
+
+        if let Some(batch) = &aggregated.balance_batch {
+            if !batch.verify()? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Simple batch-verification entry point: aggregates `proofs` and
+    /// checks the result, sharing the verification-key comparison and
+    /// balance-check setup across the whole batch. Returns `Ok(true)` only
+    /// if every proof's public inputs match and the aggregate verifies.
+    pub fn verify_batch(&self, proofs: &[ZKProof], public_inputs: &[&[u8]]) -> Result<bool, ZKError> {
+        if proofs.len() != public_inputs.len() {
+            return Err(ZKError::Verification("proofs and public_inputs length mismatch".to_string()));
+        }
+
+        for (proof, inputs) in proofs.iter().zip(public_inputs) {
+            if proof.public_inputs != *inputs {
+                return Err(ZKError::Verification("Public inputs mismatch".to_string()));
+            }
+            if proof.verification_key != self.verification_key {
+                return Err(ZKError::Verification("Verification key mismatch".to_string()));
+            }
+        }
+
+        self.verify_aggregated(&ProofAggregator::aggregate(proofs)?)
+    }
+}
+
+/// Folds `proofs` into one [`BalanceBatch`] via random linear combination,
+/// or `None` if there are no balance proofs to fold.
+fn fold_balance_proofs<'a>(proofs: impl Iterator<Item = &'a BalanceProof>) -> Result<Option<BalanceBatch>, ZKError> {
+    let proofs: Vec<&BalanceProof> = proofs.collect();
+    if proofs.is_empty() {
+        return Ok(None);
+    }
+
+    // Bind every proof's random weight to every commitment in the batch, so
+    // the weights can't be predicted before the commitments are fixed.
+    let mut batch_hasher = Sha256::new();
+    for proof in &proofs {
+        batch_hasher.update(proof.input_commitment.as_bytes());
+        batch_hasher.update(proof.output_commitment.as_bytes());
+        batch_hasher.update(proof.fee_commitment.as_bytes());
+    }
+    let batch_tag: [u8; 32] = batch_hasher.finalize().into();
+
+    let mut combined_commitment: Option<ValueCommitment> = None;
+    let mut combined_excess = ValueBlindingFactor::zero();
+
+    for (i, proof) in proofs.iter().enumerate() {
+        let weight = batch_weight(&batch_tag, i);
+
+        let diff = (&(&proof.input_commitment - &proof.output_commitment)? - &proof.fee_commitment)?;
+        let weighted = diff.scale(weight)?;
+        combined_commitment = Some(match combined_commitment {
+            None => weighted,
+            Some(acc) => (&acc + &weighted)?,
+        });
+
+        combined_excess = combined_excess + proof.blinding_excess.scale(weight);
+    }
+
+    Ok(Some(BalanceBatch {
+        combined_commitment: combined_commitment.expect("at least one proof was folded"),
+        combined_excess,
+    }))
+}
+
+/// Derives the `i`th proof's random Fiat-Shamir weight for the RLC batch
+/// bound to `batch_tag`.
+fn batch_weight(batch_tag: &[u8; 32], i: usize) -> Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(batch_tag);
+    hasher.update(i.to_le_bytes());
+    let digest: [u8; 32] = hasher.finalize().into();
+    Scalar::from_repr(digest.into()).unwrap_or(Scalar::from(1u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{circuits, ZKProver, ZKVerifier};
+
+    fn proof_with_balance(input: u64, output: u64, fee: u64) -> (ZKProof, ZKVerifier) {
+        let circuit = circuits::create_transaction_privacy_circuit();
+        let prover = ZKProver::new(circuit).unwrap();
+
+        let mut private_inputs = input.to_le_bytes().to_vec();
+        private_inputs.extend_from_slice(&output.to_le_bytes());
+        private_inputs.extend_from_slice(&fee.to_le_bytes());
+
+        let proof = prover.generate_proof(&private_inputs, &[]).unwrap();
+        let verifier = ZKVerifier::new(proof.verification_key.clone());
+        (proof, verifier)
+    }
+
+    #[test]
+    fn test_verify_batch_accepts_all_valid_proofs() {
+        let (proof_a, verifier) = proof_with_balance(500, 490, 10);
+        let (proof_b, _) = proof_with_balance(600, 590, 10);
+
+        assert!(verifier
+            .verify_batch(&[proof_a, proof_b], &[&[][..], &[][..]])
+            .unwrap());
+    }
+
+    #[test]
+    fn test_verify_aggregated_rejects_if_any_balance_is_unconserved() {
+        let (mut proof_a, verifier) = proof_with_balance(500, 490, 10);
+        let (proof_b, _) = proof_with_balance(600, 590, 10);
+
+        // Tamper with the first proof's balance proof so it no longer
+        // conserves value, while keeping it well-formed and decodable.
+        let mut bundle: ProofBundle = serde_json::from_slice(&proof_a.proof_data).unwrap();
+        bundle.balance_proof = Some(BalanceProof::prove(600, 590, 5));
+        proof_a.proof_data = serde_json::to_vec(&bundle).unwrap();
+
+        let aggregated = ProofAggregator::aggregate(&[proof_a, proof_b]).unwrap();
+        assert!(!verifier.verify_aggregated(&aggregated).unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_rejects_mismatched_verification_keys() {
+        let (proof_a, _) = proof_with_balance(500, 490, 10);
+        let mut proof_b = proof_with_balance(600, 590, 10).0;
+        proof_b.verification_key = vec![0xff; 8];
+
+        assert!(ProofAggregator::aggregate(&[proof_a, proof_b]).is_err());
+    }
+}
@@ -8,6 +8,13 @@ use sha2::{Sha256, Digest};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+pub mod aggregation;
+pub mod identity;
+pub mod range_proof;
+pub mod value_commitment;
+
+use value_commitment::{BalanceProof, ValueBlindingFactor, ValueCommitment};
+
 /// ZK-STARK proof structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZKProof {
@@ -74,68 +81,123 @@ pub enum ConstraintType {
 pub struct CircuitParameters {
     /// Field size
     pub field_size: u64,
-    
+
     /// Number of constraints
     pub num_constraints: usize,
-    
+
     /// Security parameter
     pub security_parameter: u64,
 }
 
-/// ZK-STARK prover
-pub struct ZKProver {
+/// A proving key, opaque to everything but the prover that produced it.
+pub type ProvingKey = Vec<u8>;
+
+/// A verification key: the only thing a verifier needs to check a proof of
+/// a given circuit, regardless of the circuit's concrete type.
+pub type VerificationKey = Vec<u8>;
+
+/// A circuit definition that can be compiled into a proving/verification
+/// key pair and whose constraints can be validated generically, so
+/// `ZKProver`/`circuits` aren't hard-wired to a single concrete circuit
+/// struct. Downstream crates can implement this for their own circuits
+/// (nullifier checks, membership proofs, ...) and verify them through the
+/// same `verify` free function.
+pub trait Circuit {
+    /// A stable identifier for this circuit, folded into its compiled keys.
+    fn circuit_id(&self) -> &str;
+
+    /// This circuit's field/constraint-count/security parameters.
+    fn parameters(&self) -> &CircuitParameters;
+
+    /// Every constraint (input and output) this circuit enforces.
+    fn constraints(&self) -> Vec<&Constraint>;
+
+    /// Compiles this circuit into a fresh proving/verification key pair.
+    /// The default derives both from a hash of `circuit_id`/`field_size`,
+    /// the same placeholder scheme `ZKProver` always used; circuits with a
+    /// real trusted setup should override this.
+    fn compile(&self) -> Result<(ProvingKey, VerificationKey), ZKError> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.circuit_id().as_bytes());
+        hasher.update(self.parameters().field_size.to_le_bytes());
+        let verification_key = hasher.finalize().to_vec();
+        // TODO: derive a distinct proving trapdoor once a real setup ceremony exists.
+        let proving_key = verification_key.clone();
+        Ok((proving_key, verification_key))
+    }
+}
+
+impl Circuit for TransactionPrivacyCircuit {
+    fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    fn parameters(&self) -> &CircuitParameters {
+        &self.parameters
+    }
+
+    fn constraints(&self) -> Vec<&Constraint> {
+        self.input_constraints.iter().chain(&self.output_constraints).collect()
+    }
+}
+
+/// ZK-STARK prover, generic over any `Circuit` implementation.
+pub struct ZKProver<C: Circuit> {
     /// Circuit definition
-    circuit: TransactionPrivacyCircuit,
-    
+    circuit: C,
+
     /// Proving key
-    proving_key: Vec<u8>,
+    proving_key: ProvingKey,
+
+    /// Verification key, compiled alongside the proving key so it doesn't
+    /// need recomputing on every `generate_proof` call.
+    verification_key: VerificationKey,
 }
 
-impl ZKProver {
-    /// Create a new ZK prover
-    pub fn new(circuit: TransactionPrivacyCircuit, proving_key: Vec<u8>) -> Self {
-        Self {
+impl<C: Circuit> ZKProver<C> {
+    /// Create a new ZK prover by compiling `circuit`'s key pair.
+    pub fn new(circuit: C) -> Result<Self, ZKError> {
+        let (proving_key, verification_key) = circuit.compile()?;
+        Ok(Self {
             circuit,
             proving_key,
-        }
+            verification_key,
+        })
     }
-    
+
     /// Generate a ZK-STARK proof
     pub fn generate_proof(&self, private_inputs: &[u8], public_inputs: &[u8]) -> Result<ZKProof, ZKError> {
         // Validate inputs
         self.validate_inputs(private_inputs, public_inputs)?;
-        
+
         // Generate proof using the circuit
         let proof_data = self.generate_proof_data(private_inputs, public_inputs)?;
-        
-        // Create verification key
-        let verification_key = self.generate_verification_key()?;
-        
+
         Ok(ZKProof {
             id: Uuid::new_v4(),
             proof_data,
             public_inputs: public_inputs.to_vec(),
-            verification_key,
+            verification_key: self.verification_key.clone(),
             timestamp: chrono::Utc::now().timestamp() as u64,
         })
     }
-    
+
     /// Validate inputs against circuit constraints
     fn validate_inputs(&self, private_inputs: &[u8], public_inputs: &[u8]) -> Result<(), ZKError> {
         // Check input length constraints
-        if private_inputs.len() > self.circuit.parameters.field_size as usize {
+        if private_inputs.len() > self.circuit.parameters().field_size as usize {
             return Err(ZKError::InputValidation("Private inputs too long".to_string()));
         }
-        
-        if public_inputs.len() > self.circuit.parameters.field_size as usize {
+
+        if public_inputs.len() > self.circuit.parameters().field_size as usize {
             return Err(ZKError::InputValidation("Public inputs too long".to_string()));
         }
-        
+
         // Validate constraints
-        for constraint in &self.circuit.input_constraints {
+        for constraint in self.circuit.constraints() {
             self.validate_constraint(constraint, private_inputs, public_inputs)?;
         }
-        
+
         Ok(())
     }
     
@@ -168,27 +230,44 @@ impl ZKProver {
         Ok(())
     }
     
-    /// Validate range constraint
+    /// Validate range constraint by actually constructing the zero-knowledge
+    /// range proof this constraint's `min`/`max` bounds describe and
+    /// checking it verifies -- a value outside the bounds makes
+    /// `RangeProof::prove` itself fail.
     fn validate_range_constraint(
         &self,
-        _constraint: &Constraint,
-        _private_inputs: &[u8],
+        constraint: &Constraint,
+        private_inputs: &[u8],
         _public_inputs: &[u8],
     ) -> Result<(), ZKError> {
-        // TODO: Implement range constraint validation
-        // For now, always pass
+        let (proof, _blinding) = build_range_proof(constraint, private_inputs)?;
+        if !proof.verify()? {
+            return Err(ZKError::Verification("range proof failed self-verification".to_string()));
+        }
         Ok(())
     }
     
-    /// Validate equality constraint
+    /// Commits to a single amount under a freshly drawn blinding factor, so
+    /// a hidden value can be bound into a transaction without revealing it.
+    pub fn commit_value(&self, value: u64) -> (ValueCommitment, ValueBlindingFactor) {
+        let blinding = ValueBlindingFactor::random();
+        (ValueCommitment::commit(value, &blinding), blinding)
+    }
+
+    /// Validate equality constraint by building the homomorphic
+    /// balance-conservation proof the constraint's "input = output + fee"
+    /// equation describes and checking it verifies -- unconserved amounts
+    /// make `BalanceProof::prove`'s result fail `verify`.
     fn validate_equality_constraint(
         &self,
         _constraint: &Constraint,
-        _private_inputs: &[u8],
+        private_inputs: &[u8],
         _public_inputs: &[u8],
     ) -> Result<(), ZKError> {
-        // TODO: Implement equality constraint validation
-        // For now, always pass
+        let (input, output, fee) = parse_balance_amounts(private_inputs)?;
+        if !BalanceProof::prove(input, output, fee).verify()? {
+            return Err(ZKError::Verification("balance proof failed self-verification".to_string()));
+        }
         Ok(())
     }
     
@@ -205,42 +284,60 @@ impl ZKProver {
     }
     
     /// Validate custom constraint
+    /// Validate custom constraint. The only custom constraint implemented
+    /// so far is `"rsa_jwt"` (see [`identity`](crate::identity)'s module
+    /// docs): it enforces the zkLogin-style RSA/JWT identity binding.
+    /// Unrecognized custom constraints still pass unconditionally, pending
+    /// their own validation logic.
     fn validate_custom_constraint(
         &self,
-        _constraint: &Constraint,
-        _private_inputs: &[u8],
-        _public_inputs: &[u8],
+        constraint: &Constraint,
+        private_inputs: &[u8],
+        public_inputs: &[u8],
     ) -> Result<(), ZKError> {
-        // TODO: Implement custom constraint validation
-        // For now, always pass
-        Ok(())
+        match &constraint.constraint_type {
+            ConstraintType::Custom(name) if name == "rsa_jwt" => {
+                let private: identity::IdentityPrivateInputs = serde_json::from_slice(private_inputs)
+                    .map_err(|e| ZKError::InputValidation(format!("malformed identity private inputs: {}", e)))?;
+                let public: identity::IdentityPublicInputs = serde_json::from_slice(public_inputs)
+                    .map_err(|e| ZKError::InputValidation(format!("malformed identity public inputs: {}", e)))?;
+                identity::verify_identity_claims(&private, &public)
+            }
+            _ => Ok(()),
+        }
     }
     
-    /// Generate proof data
+    /// Generate proof data: a real range sub-proof for every `Range`
+    /// constraint on the circuit and a balance proof for an `Equality`
+    /// constraint, plus a SHA256 binding tag over the raw inputs and proving
+    /// key, serialized for the verifier to check independently.
     fn generate_proof_data(&self, private_inputs: &[u8], public_inputs: &[u8]) -> Result<Vec<u8>, ZKError> {
-        // TODO: Implement actual ZK-STARK proof generation
-        // For now, create a placeholder proof
-        
+        let mut bundle = ProofBundle::default();
+
+        for constraint in self.circuit.constraints() {
+            match constraint.constraint_type {
+                ConstraintType::Range => {
+                    let (proof, _blinding) = build_range_proof(constraint, private_inputs)?;
+                    bundle.range_proofs.push(proof);
+                }
+                ConstraintType::Equality => {
+                    let (input, output, fee) = parse_balance_amounts(private_inputs)?;
+                    bundle.balance_proof = Some(BalanceProof::prove(input, output, fee));
+                }
+                ConstraintType::Inequality | ConstraintType::Custom(_) => {}
+            }
+        }
+
+        // Remaining constraint types still fold the raw inputs and proving key
+        // into a binding tag, the same way the placeholder hash did, until
+        // their own constraint types grow real sub-proofs.
         let mut hasher = Sha256::new();
         hasher.update(private_inputs);
         hasher.update(public_inputs);
         hasher.update(&self.proving_key);
-        
-        let proof_hash = hasher.finalize();
-        Ok(proof_hash.to_vec())
-    }
-    
-    /// Generate verification key
-    fn generate_verification_key(&self) -> Result<Vec<u8>, ZKError> {
-        // TODO: Implement actual verification key generation
-        // For now, create a placeholder key
-        
-        let mut hasher = Sha256::new();
-        hasher.update(&self.circuit.circuit_id.as_bytes());
-        hasher.update(&self.circuit.parameters.field_size.to_le_bytes());
-        
-        let key_hash = hasher.finalize();
-        Ok(key_hash.to_vec())
+        bundle.binding_tag = hasher.finalize().to_vec();
+
+        serde_json::to_vec(&bundle).map_err(|e| ZKError::ProofGeneration(e.to_string()))
     }
 }
 
@@ -256,41 +353,66 @@ impl ZKVerifier {
         Self { verification_key }
     }
     
-    /// Verify a ZK-STARK proof
+    /// Verify a ZK-STARK proof against this verifier's verification key.
+    /// Delegates to the free [`verify`] function, which needs neither `self`
+    /// nor a concrete circuit type -- a verifier only ever needs the
+    /// verification key a circuit's `Circuit::compile` produced.
     pub fn verify_proof(&self, proof: &ZKProof, public_inputs: &[u8]) -> Result<bool, ZKError> {
-        // Check if public inputs match
-        if proof.public_inputs != public_inputs {
-            return Err(ZKError::Verification("Public inputs mismatch".to_string()));
-        }
-        
-        // Check if verification key matches
-        if proof.verification_key != self.verification_key {
-            return Err(ZKError::Verification("Verification key mismatch".to_string()));
+        verify(&self.verification_key, proof, public_inputs)
+    }
+
+    /// Verifies only the homomorphic balance-conservation proof embedded in
+    /// `proof`, independent of its range sub-proofs. Returns an error if
+    /// `proof` doesn't carry a balance proof (i.e. its circuit declared no
+    /// `Equality` constraint).
+    pub fn verify_balance(&self, proof: &ZKProof) -> Result<bool, ZKError> {
+        let bundle = decode_proof_bundle(proof)?;
+        let balance_proof = bundle
+            .balance_proof
+            .ok_or_else(|| ZKError::Verification("proof carries no balance proof".to_string()))?;
+        balance_proof.verify()
+    }
+}
+
+fn decode_proof_bundle(proof: &ZKProof) -> Result<ProofBundle, ZKError> {
+    serde_json::from_slice(&proof.proof_data)
+        .map_err(|e| ZKError::Verification(format!("malformed proof data: {}", e)))
+}
+
+/// Verifies `proof` against a raw `verification_key`, independent of any
+/// `ZKVerifier`/circuit type -- the only state a proof's verification ever
+/// needs is the verification key its circuit compiled to.
+pub fn verify(verification_key: &[u8], proof: &ZKProof, public_inputs: &[u8]) -> Result<bool, ZKError> {
+    if proof.public_inputs != public_inputs {
+        return Err(ZKError::Verification("Public inputs mismatch".to_string()));
+    }
+
+    if proof.verification_key != verification_key {
+        return Err(ZKError::Verification("Verification key mismatch".to_string()));
+    }
+
+    check_proof_bundle(proof)
+}
+
+/// Checks every embedded range sub-proof and the balance proof (if any),
+/// each of which is self-contained (it carries its own commitments/bounds),
+/// so this needs no access to a prover's circuit or a verifier's key.
+fn check_proof_bundle(proof: &ZKProof) -> Result<bool, ZKError> {
+    let bundle = decode_proof_bundle(proof)?;
+
+    for range_proof in &bundle.range_proofs {
+        if !range_proof.verify()? {
+            return Ok(false);
         }
-        
-        // Verify the proof
-        let is_valid = self.verify_proof_data(proof, public_inputs)?;
-        
-        Ok(is_valid)
     }
-    
-    /// Verify proof data
-    fn verify_proof_data(&self, proof: &ZKProof, public_inputs: &[u8]) -> Result<bool, ZKError> {
-        // TODO: Implement actual ZK-STARK proof verification
-        // For now, create a placeholder verification
-        
-        let mut hasher = Sha256::new();
-        hasher.update(&proof.proof_data);
-        hasher.update(public_inputs);
-        hasher.update(&self.verification_key);
-        
-        let verification_hash = hasher.finalize();
-        
-        // For placeholder implementation, consider proof valid if hash is not all zeros
-        let is_valid = verification_hash.iter().any(|&b| b != 0);
-        
-        Ok(is_valid)
+
+    if let Some(balance_proof) = &bundle.balance_proof {
+        if !balance_proof.verify()? {
+            return Ok(false);
+        }
     }
+
+    Ok(true)
 }
 
 /// ZK-STARK error types
@@ -312,6 +434,77 @@ pub enum ZKError {
     Internal(String),
 }
 
+/// The real sub-proofs carried in a `ZKProof`'s `proof_data`, replacing the
+/// placeholder SHA256 digest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ProofBundle {
+    range_proofs: Vec<range_proof::RangeProof>,
+    /// Present whenever the circuit declares an `Equality` "input equals
+    /// output plus fee" constraint; proves value conservation without
+    /// revealing any of the three amounts.
+    balance_proof: Option<BalanceProof>,
+    /// Binds the raw private/public inputs and proving key together until
+    /// the remaining constraint types (inequality, custom) grow their own
+    /// sub-proofs the way `Range` and `Equality` just did.
+    binding_tag: Vec<u8>,
+}
+
+/// Parses a `Range` constraint's `min`/`max` parameters, inserted by
+/// `circuits::create_transaction_privacy_circuit`/`create_balance_proof_circuit`
+/// as decimal strings.
+fn parse_range_bounds(constraint: &Constraint) -> Result<(u64, u64), ZKError> {
+    let parse = |key: &str| -> Result<u64, ZKError> {
+        constraint
+            .parameters
+            .get(key)
+            .ok_or_else(|| ZKError::InputValidation(format!("range constraint missing '{}'", key)))?
+            .parse::<u64>()
+            .map_err(|e| ZKError::InputValidation(format!("range constraint '{}' is not a u64: {}", key, e)))
+    };
+    Ok((parse("min")?, parse("max")?))
+}
+
+/// The value a range constraint is proved over. This module's inputs are
+/// undifferentiated byte blobs rather than typed per-constraint fields, so
+/// by convention the value lives in the first 8 bytes, little-endian.
+fn parse_range_value(private_inputs: &[u8]) -> Result<u64, ZKError> {
+    let bytes: [u8; 8] = private_inputs
+        .get(0..8)
+        .ok_or_else(|| ZKError::InputValidation("private inputs too short to contain a range value".to_string()))?
+        .try_into()
+        .unwrap();
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// The amounts a balance (`Equality`) constraint is proved over. Like
+/// `parse_range_value`, this module's inputs are undifferentiated byte
+/// blobs, so by convention the three u64 amounts the "input = output +
+/// fee" equation relates live back-to-back, little-endian, starting at the
+/// range value: `[value:8][output:8][fee:8]`, with the leading `value`
+/// doubling as the aggregate input amount.
+fn parse_balance_amounts(private_inputs: &[u8]) -> Result<(u64, u64, u64), ZKError> {
+    let read_u64 = |range: std::ops::Range<usize>| -> Result<u64, ZKError> {
+        let bytes: [u8; 8] = private_inputs
+            .get(range)
+            .ok_or_else(|| ZKError::InputValidation("private inputs too short to contain a balance amount".to_string()))?
+            .try_into()
+            .unwrap();
+        Ok(u64::from_le_bytes(bytes))
+    };
+    Ok((read_u64(0..8)?, read_u64(8..16)?, read_u64(16..24)?))
+}
+
+/// Builds the zero-knowledge range proof a `Range` constraint describes,
+/// for the value encoded in `private_inputs`.
+fn build_range_proof(
+    constraint: &Constraint,
+    private_inputs: &[u8],
+) -> Result<(range_proof::RangeProof, k256::Scalar), ZKError> {
+    let (min, max) = parse_range_bounds(constraint)?;
+    let value = parse_range_value(private_inputs)?;
+    range_proof::RangeProof::prove(value, min, max)
+}
+
 /// Predefined circuits for common use cases
 pub mod circuits {
     use super::*;
@@ -359,7 +552,7 @@ pub mod circuits {
             input_constraints,
             output_constraints,
             parameters: CircuitParameters {
-                field_size: 2u64.pow(64),
+                field_size: u64::MAX,
                 num_constraints: 1000,
                 security_parameter: 128,
             },
@@ -386,12 +579,34 @@ pub mod circuits {
             input_constraints: input_constraints,
             output_constraints: Vec::new(),
             parameters: CircuitParameters {
-                field_size: 2u64.pow(64),
+                field_size: u64::MAX,
                 num_constraints: 500,
                 security_parameter: 128,
             },
         }
     }
+
+    /// Create a zkLogin-style identity circuit: proves ownership of an
+    /// OAuth/JWT identity bound to an ephemeral key without revealing
+    /// either, per the `"rsa_jwt"` custom constraint documented in
+    /// [`identity`](crate::identity).
+    pub fn create_zk_identity_circuit() -> TransactionPrivacyCircuit {
+        let input_constraints = vec![Constraint {
+            constraint_type: ConstraintType::Custom("rsa_jwt".to_string()),
+            parameters: HashMap::new(),
+        }];
+
+        TransactionPrivacyCircuit {
+            circuit_id: "zk_identity_v1".to_string(),
+            input_constraints,
+            output_constraints: Vec::new(),
+            parameters: CircuitParameters {
+                field_size: u64::MAX,
+                num_constraints: 1,
+                security_parameter: 128,
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -426,18 +641,48 @@ mod tests {
     #[test]
     fn test_prover_creation() {
         let circuit = circuits::create_transaction_privacy_circuit();
-        let proving_key = vec![1, 2, 3, 4];
-        
-        let prover = ZKProver::new(circuit, proving_key);
-        
-        assert_eq!(prover.proving_key.len(), 4);
+
+        let prover = ZKProver::new(circuit).unwrap();
+
+        assert_eq!(prover.proving_key.len(), 32);
     }
     
     #[test]
     fn test_verifier_creation() {
         let verification_key = vec![1, 2, 3, 4];
         let verifier = ZKVerifier::new(verification_key);
-        
+
         assert_eq!(verifier.verification_key.len(), 4);
     }
+
+    #[test]
+    fn test_generate_and_verify_proof_checks_balance_conservation() {
+        let circuit = circuits::create_transaction_privacy_circuit();
+        let prover = ZKProver::new(circuit).unwrap();
+
+        // [value:8][output:8][fee:8], with value doubling as the amount and
+        // fee range proofs' input, per `parse_range_value`/`parse_balance_amounts`.
+        let mut private_inputs = 500u64.to_le_bytes().to_vec();
+        private_inputs.extend_from_slice(&490u64.to_le_bytes());
+        private_inputs.extend_from_slice(&10u64.to_le_bytes());
+        let public_inputs = vec![];
+
+        let proof = prover.generate_proof(&private_inputs, &public_inputs).unwrap();
+        let verifier = ZKVerifier::new(proof.verification_key.clone());
+
+        assert!(verifier.verify_proof(&proof, &public_inputs).unwrap());
+        assert!(verifier.verify_balance(&proof).unwrap());
+    }
+
+    #[test]
+    fn test_generate_proof_rejects_unconserved_balance() {
+        let circuit = circuits::create_transaction_privacy_circuit();
+        let prover = ZKProver::new(circuit).unwrap();
+
+        let mut private_inputs = 500u64.to_le_bytes().to_vec();
+        private_inputs.extend_from_slice(&490u64.to_le_bytes());
+        private_inputs.extend_from_slice(&5u64.to_le_bytes());
+
+        assert!(prover.validate_inputs(&private_inputs, &[]).is_err());
+    }
 }
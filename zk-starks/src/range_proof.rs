@@ -0,0 +1,299 @@
+//! Pedersen-commitment range proofs for the `ConstraintType::Range` path.
+//!
+//! Follows the Camenisch-Chaabouni-shelat u-ary set-membership idea --
+//! decompose the value into digits, commit to each digit, and prove each
+//! digit is a valid member of its digit set -- but substitutes a Schnorr
+//! OR-proof (a standard 1-of-n disjunctive sigma protocol) for the
+//! original scheme's Boneh-Boyen pairing signatures, since this crate has
+//! no pairing-friendly curve dependency. `u = 2` (binary digits) keeps the
+//! per-digit branching factor, and so the proof size, minimal; the same
+//! construction generalizes to larger `u` by adding branches per digit.
+//!
+//! An arbitrary range `[min, max]` is proved the way the request describes:
+//! proving `v - min` and `max - v` both lie in `[0, 2^bits)`, where `bits`
+//! is the smallest bit-width whose `2^bits` exceeds `max - min`. Both sub-proofs are
+//! shifts of the same original commitment, so they can't be satisfied by
+//! two different, inconsistent values of `v`.
+
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
+use k256::elliptic_curve::PrimeField;
+use k256::{ProjectivePoint, Scalar, Secp256k1};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::ZKError;
+
+/// Pedersen generator `H`, derived via hash-to-curve so nobody (including
+/// the prover) knows its discrete log relative to the curve's standard
+/// generator `G` -- that unknown relationship is what makes `v*G + r*H`
+/// binding to `v` while `r` keeps it hiding.
+pub fn pedersen_h() -> ProjectivePoint {
+    Secp256k1::hash_from_bytes::<ExpandMsgXmd<Sha256>>(&[b"value-commitment"], &[b"SDUPI-ZK-PEDERSEN-H-v1"])
+        .expect("hash-to-curve with a fixed, valid DST never fails")
+}
+
+/// Commits to `value` under blinding factor `blinding`: `value*G + blinding*H`.
+pub fn commit(value: u64, blinding: Scalar) -> ProjectivePoint {
+    ProjectivePoint::GENERATOR * Scalar::from(value) + pedersen_h() * blinding
+}
+
+pub(crate) fn point_to_bytes(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_bytes().to_vec()
+}
+
+pub(crate) fn point_from_bytes(bytes: &[u8]) -> Result<ProjectivePoint, ZKError> {
+    let repr: [u8; 33] = bytes
+        .try_into()
+        .map_err(|_| ZKError::Verification("malformed curve point in range proof".to_string()))?;
+    Option::from(ProjectivePoint::from_bytes(&repr.into()))
+        .ok_or_else(|| ZKError::Verification("point not on curve in range proof".to_string()))
+}
+
+pub(crate) fn scalar_to_bytes(scalar: &Scalar) -> [u8; 32] {
+    scalar.to_bytes().into()
+}
+
+pub(crate) fn scalar_from_bytes(bytes: &[u8; 32]) -> Result<Scalar, ZKError> {
+    Option::from(Scalar::from_repr((*bytes).into()))
+        .ok_or_else(|| ZKError::Verification("scalar out of range in range proof".to_string()))
+}
+
+/// Fiat-Shamir challenge binding a bit commitment to its OR-proof's two
+/// branch commitments, so the branch challenges can't be chosen after the
+/// fact.
+fn bit_challenge(commitment: &ProjectivePoint, a0: &ProjectivePoint, a1: &ProjectivePoint) -> Scalar {
+    let mut hasher = Sha256::new();
+    hasher.update(point_to_bytes(commitment));
+    hasher.update(point_to_bytes(a0));
+    hasher.update(point_to_bytes(a1));
+    let digest: [u8; 32] = hasher.finalize().into();
+    // The digest is taken as a scalar as-is, not reduced: one at or above the
+    // group order (probability roughly 2^-128 on secp256k1) falls back to 1.
+    Scalar::from_repr(digest.into()).unwrap_or(Scalar::from(1u64))
+}
+
+/// A 1-of-2 Schnorr OR-proof that a Pedersen commitment opens to `0` or `1`,
+/// without revealing which. Standard "bit is 0 or 1" building block for
+/// binary range proofs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitProof {
+    a0: Vec<u8>,
+    a1: Vec<u8>,
+    c0: [u8; 32],
+    c1: [u8; 32],
+    z0: [u8; 32],
+    z1: [u8; 32],
+}
+
+fn prove_bit(bit: u8, blinding: Scalar, commitment: ProjectivePoint) -> BitProof {
+    let h = pedersen_h();
+    let g = ProjectivePoint::GENERATOR;
+    let mut rng = OsRng;
+
+    // Branch `i` asserts `commitment - i*G = r*H`; only the branch matching
+    // the real bit gets an honest Schnorr proof, the other is simulated.
+    let (w_real, c_sim, z_sim) = (Scalar::generate_biased(&mut rng), Scalar::generate_biased(&mut rng), Scalar::generate_biased(&mut rng));
+
+    let (a0, a1, real_is_zero) = if bit == 0 {
+        let a0 = h * w_real;
+        let target1 = commitment - g;
+        let a1 = h * z_sim - target1 * c_sim;
+        (a0, a1, true)
+    } else {
+        let a1 = h * w_real;
+        let target0 = commitment;
+        let a0 = h * z_sim - target0 * c_sim;
+        (a0, a1, false)
+    };
+
+    let c = bit_challenge(&commitment, &a0, &a1);
+
+    if real_is_zero {
+        let c0 = c - c_sim;
+        let z0 = w_real + c0 * blinding;
+        BitProof {
+            a0: point_to_bytes(&a0),
+            a1: point_to_bytes(&a1),
+            c0: scalar_to_bytes(&c0),
+            c1: scalar_to_bytes(&c_sim),
+            z0: scalar_to_bytes(&z0),
+            z1: scalar_to_bytes(&z_sim),
+        }
+    } else {
+        let c1 = c - c_sim;
+        let z1 = w_real + c1 * blinding;
+        BitProof {
+            a0: point_to_bytes(&a0),
+            a1: point_to_bytes(&a1),
+            c0: scalar_to_bytes(&c_sim),
+            c1: scalar_to_bytes(&c1),
+            z0: scalar_to_bytes(&z_sim),
+            z1: scalar_to_bytes(&z1),
+        }
+    }
+}
+
+fn verify_bit(commitment: &ProjectivePoint, proof: &BitProof) -> Result<bool, ZKError> {
+    let h = pedersen_h();
+    let g = ProjectivePoint::GENERATOR;
+
+    let a0 = point_from_bytes(&proof.a0)?;
+    let a1 = point_from_bytes(&proof.a1)?;
+    let c0 = scalar_from_bytes(&proof.c0)?;
+    let c1 = scalar_from_bytes(&proof.c1)?;
+    let z0 = scalar_from_bytes(&proof.z0)?;
+    let z1 = scalar_from_bytes(&proof.z1)?;
+
+    let c = bit_challenge(commitment, &a0, &a1);
+    if c0 + c1 != c {
+        return Ok(false);
+    }
+
+    let target0 = *commitment;
+    let target1 = *commitment - g;
+
+    Ok(h * z0 == a0 + target0 * c0 && h * z1 == a1 + target1 * c1)
+}
+
+/// Proves a committed value lies in `[0, 2^bits)` by committing to each bit
+/// and proving the bit commitments sum (weighted by powers of two) back to
+/// the original commitment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitDecompositionProof {
+    bit_commitments: Vec<Vec<u8>>,
+    bit_proofs: Vec<BitProof>,
+    /// `blinding - sum(2^i * bit_blinding_i)`, revealed so the verifier can
+    /// confirm the decomposition commits to the same value as `commitment`
+    /// with no unaccounted-for remainder.
+    blinding_excess: [u8; 32],
+}
+
+fn prove_bits(value: u64, blinding: Scalar, bits: usize) -> Result<BitDecompositionProof, ZKError> {
+    if bits == 0 || bits > 63 {
+        return Err(ZKError::ProofGeneration("range proof bit-width must be in 1..=63".to_string()));
+    }
+    if value >= (1u64 << bits) {
+        return Err(ZKError::ProofGeneration("value does not fit in the requested bit-width".to_string()));
+    }
+
+    let mut rng = OsRng;
+    let mut bit_commitments = Vec::with_capacity(bits);
+    let mut bit_proofs = Vec::with_capacity(bits);
+    let mut weighted_blinding_sum = Scalar::ZERO;
+
+    for i in 0..bits {
+        let bit = ((value >> i) & 1) as u8;
+        let bit_blinding = Scalar::generate_biased(&mut rng);
+        let bit_commitment = commit(bit as u64, bit_blinding);
+        bit_proofs.push(prove_bit(bit, bit_blinding, bit_commitment));
+        bit_commitments.push(point_to_bytes(&bit_commitment));
+        weighted_blinding_sum += Scalar::from(1u64 << i) * bit_blinding;
+    }
+
+    Ok(BitDecompositionProof {
+        bit_commitments,
+        bit_proofs,
+        blinding_excess: scalar_to_bytes(&(blinding - weighted_blinding_sum)),
+    })
+}
+
+fn verify_bits(commitment: &ProjectivePoint, proof: &BitDecompositionProof, bits: usize) -> Result<bool, ZKError> {
+    if proof.bit_commitments.len() != bits || proof.bit_proofs.len() != bits {
+        return Ok(false);
+    }
+
+    let mut weighted_sum = ProjectivePoint::IDENTITY;
+    for (i, (commitment_bytes, bit_proof)) in proof.bit_commitments.iter().zip(&proof.bit_proofs).enumerate() {
+        let bit_commitment = point_from_bytes(commitment_bytes)?;
+        if !verify_bit(&bit_commitment, bit_proof)? {
+            return Ok(false);
+        }
+        weighted_sum += bit_commitment * Scalar::from(1u64 << i);
+    }
+
+    let excess = scalar_from_bytes(&proof.blinding_excess)?;
+    Ok(*commitment - weighted_sum == pedersen_h() * excess)
+}
+
+/// Smallest `bits` (at least 1) such that `2^bits > span`.
+fn bits_for_span(span: u64) -> usize {
+    (64 - span.leading_zeros()).max(1) as usize
+}
+
+/// A proof that a committed value `v` lies in `[min, max]`, without
+/// revealing `v`: shows `v - min` and `max - v` both lie in `[0, 2^bits)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeProof {
+    /// Pedersen commitment to the value being range-proved.
+    pub commitment: Vec<u8>,
+    pub min: u64,
+    pub max: u64,
+    bits: usize,
+    lower: BitDecompositionProof,
+    upper: BitDecompositionProof,
+}
+
+impl RangeProof {
+    /// Proves `value` (committed under a fresh blinding factor) lies in
+    /// `[min, max]`. Returns the proof along with the blinding factor, so
+    /// the caller can later open or combine the commitment.
+    pub fn prove(value: u64, min: u64, max: u64) -> Result<(Self, Scalar), ZKError> {
+        if min > max || value < min || value > max {
+            return Err(ZKError::ProofGeneration(format!("value {} is outside [{}, {}]", value, min, max)));
+        }
+
+        let bits = bits_for_span(max - min);
+        let blinding = Scalar::generate_biased(&mut OsRng);
+        let commitment = commit(value, blinding);
+
+        let lower = prove_bits(value - min, blinding, bits)?;
+        // `max*G - commitment` has value `max - v` under blinding `-blinding`.
+        let upper = prove_bits(max - value, -blinding, bits)?;
+
+        Ok((
+            RangeProof { commitment: point_to_bytes(&commitment), min, max, bits, lower, upper },
+            blinding,
+        ))
+    }
+
+    /// Checks the proof is internally consistent and proves its committed
+    /// value lies in `[self.min, self.max]`.
+    pub fn verify(&self) -> Result<bool, ZKError> {
+        let commitment = point_from_bytes(&self.commitment)?;
+        let g = ProjectivePoint::GENERATOR;
+
+        let lower_commitment = commitment - g * Scalar::from(self.min);
+        let upper_commitment = g * Scalar::from(self.max) - commitment;
+
+        Ok(verify_bits(&lower_commitment, &self.lower, self.bits)?
+            && verify_bits(&upper_commitment, &self.upper, self.bits)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_proof_accepts_value_in_range() {
+        let (proof, _blinding) = RangeProof::prove(42, 0, 1_000_000_000).unwrap();
+        assert!(proof.verify().unwrap());
+    }
+
+    #[test]
+    fn test_range_proof_rejects_out_of_range_value() {
+        assert!(RangeProof::prove(10, 20, 30).is_err());
+    }
+
+    #[test]
+    fn test_tampered_range_proof_fails_verification() {
+        let (mut proof, _blinding) = RangeProof::prove(5, 0, 255).unwrap();
+        // Swap in another valid point: still well-formed, but inconsistent
+        // with the committed value's bit decomposition.
+        let (other, _) = RangeProof::prove(5, 0, 255).unwrap();
+        proof.commitment = other.lower.bit_commitments[0].clone();
+        assert!(!proof.verify().unwrap());
+    }
+}
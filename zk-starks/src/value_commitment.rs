@@ -0,0 +1,180 @@
+//! Pedersen value commitments and a homomorphic balance-conservation check
+//! for the `ConstraintType::Equality` "input = output + fee" path.
+//!
+//! A `ValueCommitment` is `v*G + r*H` (the same commitment scheme
+//! [`range_proof`](crate::range_proof) uses for bounding values, and built
+//! on the same hash-derived generator `H`). Pedersen commitments are
+//! additively homomorphic: `commit(a, r_a) + commit(b, r_b) == commit(a+b,
+//! r_a+r_b)`. So conservation of value -- `input == output + fee` -- can be
+//! checked on the commitments alone, without ever decommitting the amounts:
+//! `C_in - C_out - C_fee` is a commitment to zero with blinding factor
+//! `r_in - r_out - r_fee`; the prover reveals that blinding difference (the
+//! `ValueBlindingFactor` "excess") and the verifier checks the point equals
+//! `excess*H` exactly, which can only hold if the committed values summed
+//! to zero.
+
+use k256::{ProjectivePoint, Scalar};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+
+use crate::range_proof::{pedersen_h, point_from_bytes, point_to_bytes, scalar_from_bytes, scalar_to_bytes};
+use crate::ZKError;
+
+/// A blinding (randomness) factor for a [`ValueCommitment`]. Supports the
+/// add/sub used to fold many commitments' randomness into a single
+/// revealed excess for [`verify_zero`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ValueBlindingFactor([u8; 32]);
+
+impl ValueBlindingFactor {
+    /// Draws a fresh random blinding factor via `Scalar::generate_biased`.
+    pub fn random() -> Self {
+        Self::from_scalar(Scalar::generate_biased(&mut OsRng))
+    }
+
+    fn from_scalar(scalar: Scalar) -> Self {
+        Self(scalar_to_bytes(&scalar))
+    }
+
+    fn scalar(&self) -> Scalar {
+        // Only ever constructed from a valid Scalar via `from_scalar`/`random`,
+        // so the bytes always round-trip.
+        scalar_from_bytes(&self.0).expect("ValueBlindingFactor always wraps a valid scalar")
+    }
+
+    /// The additive identity, useful as the starting accumulator for a fold.
+    pub(crate) fn zero() -> Self {
+        Self::from_scalar(Scalar::ZERO)
+    }
+
+    /// Scales this blinding factor by `weight`, mirroring
+    /// [`ValueCommitment::scale`] so the two stay usable together in a
+    /// random-linear-combination fold.
+    pub(crate) fn scale(&self, weight: Scalar) -> Self {
+        Self::from_scalar(self.scalar() * weight)
+    }
+}
+
+impl std::ops::Add for ValueBlindingFactor {
+    type Output = ValueBlindingFactor;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_scalar(self.scalar() + rhs.scalar())
+    }
+}
+
+impl std::ops::Sub for ValueBlindingFactor {
+    type Output = ValueBlindingFactor;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::from_scalar(self.scalar() - rhs.scalar())
+    }
+}
+
+/// A Pedersen commitment `C = v*G + r*H` hiding a value `v` behind blinding
+/// factor `r`, with the homomorphic `+`/`-` needed to check value
+/// conservation across many commitments without opening any of them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueCommitment(Vec<u8>);
+
+impl ValueCommitment {
+    /// Commits to `value` under `blinding`.
+    pub fn commit(value: u64, blinding: &ValueBlindingFactor) -> Self {
+        Self(point_to_bytes(&crate::range_proof::commit(value, blinding.scalar())))
+    }
+
+    fn point(&self) -> Result<ProjectivePoint, ZKError> {
+        point_from_bytes(&self.0)
+    }
+
+    /// Scales this commitment by `weight`: used to fold many balance
+    /// proofs' commitments into one random-linear-combination check.
+    pub(crate) fn scale(&self, weight: Scalar) -> Result<Self, ZKError> {
+        Ok(Self(point_to_bytes(&(self.point()? * weight))))
+    }
+
+    /// Raw serialized curve point, for hashing into a Fiat-Shamir challenge.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::ops::Add<&ValueCommitment> for &ValueCommitment {
+    type Output = Result<ValueCommitment, ZKError>;
+    fn add(self, rhs: &ValueCommitment) -> Self::Output {
+        Ok(ValueCommitment(point_to_bytes(&(self.point()? + rhs.point()?))))
+    }
+}
+
+impl std::ops::Sub<&ValueCommitment> for &ValueCommitment {
+    type Output = Result<ValueCommitment, ZKError>;
+    fn sub(self, rhs: &ValueCommitment) -> Self::Output {
+        Ok(ValueCommitment(point_to_bytes(&(self.point()? - rhs.point()?))))
+    }
+}
+
+/// Checks that `commitment` commits to zero under the revealed `excess`
+/// blinding factor, i.e. `commitment == excess*H`. This is the check that
+/// proves value conservation: call it on `C_in - C_out - C_fee` with
+/// `r_in - r_out - r_fee` as the excess.
+pub fn verify_zero(commitment: &ValueCommitment, excess: &ValueBlindingFactor) -> Result<bool, ZKError> {
+    Ok(commitment.point()? == pedersen_h() * excess.scalar())
+}
+
+/// A conservation-of-value proof: commitments to the aggregate input
+/// amount, aggregate output amount, and fee, plus the blinding-factor
+/// excess that lets a verifier confirm `input - output - fee` commits to
+/// zero without learning any of the three amounts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceProof {
+    pub input_commitment: ValueCommitment,
+    pub output_commitment: ValueCommitment,
+    pub fee_commitment: ValueCommitment,
+    pub blinding_excess: ValueBlindingFactor,
+}
+
+impl BalanceProof {
+    /// Commits to `input`, `output`, and `fee` with fresh blinding factors
+    /// and produces the excess that proves `input == output + fee`.
+    pub fn prove(input: u64, output: u64, fee: u64) -> Self {
+        let (input_blind, output_blind, fee_blind) =
+            (ValueBlindingFactor::random(), ValueBlindingFactor::random(), ValueBlindingFactor::random());
+
+        BalanceProof {
+            input_commitment: ValueCommitment::commit(input, &input_blind),
+            output_commitment: ValueCommitment::commit(output, &output_blind),
+            fee_commitment: ValueCommitment::commit(fee, &fee_blind),
+            blinding_excess: input_blind - output_blind - fee_blind,
+        }
+    }
+
+    /// Verifies `input_commitment - output_commitment - fee_commitment`
+    /// commits to zero under `blinding_excess`.
+    pub fn verify(&self) -> Result<bool, ZKError> {
+        let diff = (&(&self.input_commitment - &self.output_commitment)? - &self.fee_commitment)?;
+        verify_zero(&diff, &self.blinding_excess)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_proof_accepts_conserved_amounts() {
+        let proof = BalanceProof::prove(110, 100, 10);
+        assert!(proof.verify().unwrap());
+    }
+
+    #[test]
+    fn test_balance_proof_rejects_unconserved_amounts() {
+        let proof = BalanceProof::prove(110, 100, 5);
+        assert!(!proof.verify().unwrap());
+    }
+
+    #[test]
+    fn test_value_commitment_is_additively_homomorphic() {
+        let (ba, bb) = (ValueBlindingFactor::random(), ValueBlindingFactor::random());
+        let (ca, cb) = (ValueCommitment::commit(7, &ba), ValueCommitment::commit(3, &bb));
+        let sum = (&ca + &cb).unwrap();
+        assert_eq!(sum.0, ValueCommitment::commit(10, &(ba + bb)).0);
+    }
+}